@@ -1,45 +1,188 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
-use crate::git::branch::current_branch;
-use crate::git::repo::repo_root;
+use crate::config::{Config, data_dir};
+use crate::git::repo::GitRepo;
+use crate::i18n::tr;
+use crate::log::archive;
+use crate::log::registry::Registry;
 use crate::log::store::LogStore;
 
+/// 디렉터리 구분 없이 쓰는 공용 임시 로그북 이름
+pub const GLOBAL_SCRATCH: &str = "scratch";
+
 #[derive(Debug)]
 pub struct AppState {
+    /// git repo root (repo 밖이면 현재 디렉터리)
     pub repo_root: PathBuf,
+    pub in_repo: bool,
+    /// repo 밖이면 None
+    pub git: Option<GitRepo>,
+    /// 현재 브랜치, 임시 로그북 모드에서는 임시 로그북 이름
     pub current_branch: String,
+    /// repo 밖이거나 브랜치가 없어서 임시 로그북에 기록 중
+    pub scratch: bool,
     pub log_store: LogStore,
+    pub config: Config,
 
     last_branch_check: Instant,
 }
 
 impl AppState {
     pub fn init() -> Result<Self, String> {
-        let repo_root = repo_root()?;
-        let branch = current_branch()?;
-        let log_store = LogStore::new(&repo_root)?;
+        Self::open(Workspace::discover()?)
+    }
+
+    /// main에서 이미 찾아 둔 repo와 설정으로 상태를 만든다
+    pub fn open(workspace: Workspace) -> Result<Self, String> {
+        let Workspace { root, git, config } = workspace;
+        let config = config?;
+        let in_repo = git.is_some();
+
+        let branch = git.as_ref().and_then(|git| git.current_branch().ok());
+        let (current_branch, scratch, log_store) = match branch {
+            Some(branch) => (branch, false, LogStore::new(&config.log_dir(&root))?),
+            None => (
+                scratch_name(&config, &root),
+                true,
+                LogStore::new(&config.scratch_dir()?)?,
+            ),
+        };
 
         Ok(Self {
-            repo_root,
-            current_branch: branch,
+            repo_root: root,
+            in_repo,
+            git,
+            current_branch,
+            scratch,
             log_store,
+            config,
             last_branch_check: Instant::now(),
         })
     }
 
-    /// 일정 주기마다 브랜치 갱신
+    /// `--branch`로 지정한 브랜치를 대상으로 삼는다 (브랜치 없는 repo에서도 repo 로그북 사용)
+    pub fn select_branch(&mut self, branch: String) -> Result<(), String> {
+        if self.scratch && self.in_repo {
+            self.log_store = self.repo_store()?;
+            self.scratch = false;
+        }
+        self.current_branch = branch;
+        Ok(())
+    }
+
+    /// global.enabled면 전역 목록에 이 repo를 등록한다 (임시 로그북은 제외).
+    /// 등록 실패는 로그 작성에 영향을 주지 않는다.
+    pub fn register_globally(&self) {
+        if self.config.global.enabled && !self.scratch {
+            let _ = register_repo(&self.repo_root, &self.config.log_dir(&self.repo_root));
+        }
+    }
+
+    /// 임시 로그북 저장소 (repo 모드에서도 노트를 옮겨 올 때 사용)
+    pub fn scratch_store(&self) -> Result<LogStore, String> {
+        LogStore::new(&self.config.scratch_dir()?)
+    }
+
+    /// repo 로그북 저장소 (임시 로그북 모드에서도 repo 쪽을 가리킴)
+    pub fn repo_store(&self) -> Result<LogStore, String> {
+        LogStore::new(&self.config.log_dir(&self.repo_root))
+    }
+
+    /// 로컬에도 원격에도 브랜치가 없는 repo 로그북
+    pub fn orphaned_logbooks(&self) -> Result<Vec<String>, String> {
+        let git = self.git.as_ref().ok_or_else(|| tr!("git.not_repo"))?;
+        archive::orphaned(&self.repo_store()?, &git.live_branches()?)
+    }
+
+    /// 이 디렉터리의 임시 로그북 이름
+    pub fn scratch_name(&self) -> String {
+        scratch_name(&self.config, &self.repo_root)
+    }
+
+    /// 파일 감시를 못 쓸 때 일정 주기마다 브랜치 갱신
     pub fn refresh_branch_if_needed(&mut self) {
-        // 500ms에 한 번만 체크
-        if self.last_branch_check.elapsed() < Duration::from_millis(500) {
+        // 설정된 주기(git.branch_poll_ms)에 한 번만 체크
+        let interval = Duration::from_millis(self.config.git.branch_poll_ms);
+        if self.last_branch_check.elapsed() < interval {
             return;
         }
         self.last_branch_check = Instant::now();
+        self.refresh_branch();
+    }
+
+    /// 현재 브랜치를 다시 확인한다 (.git/HEAD가 바뀌었을 때)
+    pub fn refresh_branch(&mut self) {
+        let Some(git) = &self.git else {
+            return;
+        };
 
-        if let Ok(branch) = current_branch() {
-            if branch != self.current_branch {
-                self.current_branch = branch;
+        match git.current_branch() {
+            Ok(branch) if self.scratch => {
+                // 브랜치가 생기면 repo 로그북으로 돌아간다
+                if let Ok(store) = self.repo_store() {
+                    self.log_store = store;
+                    self.current_branch = branch;
+                    self.scratch = false;
+                }
+            }
+            Ok(branch) => {
+                if branch != self.current_branch {
+                    self.current_branch = branch;
+                }
+            }
+            Err(_) if !self.scratch => {
+                // detached HEAD 등 브랜치가 없어지면 임시 로그북으로
+                if let Ok(store) = self.scratch_store() {
+                    self.log_store = store;
+                    self.current_branch = self.scratch_name();
+                    self.scratch = true;
+                }
             }
+            Err(_) => {}
         }
     }
 }
+
+/// 시작할 때 한 번 찾아 두고 명령마다 다시 쓰는 작업 위치와 설정
+#[derive(Debug)]
+pub struct Workspace {
+    /// git repo root (repo 밖이면 현재 디렉터리)
+    pub root: PathBuf,
+    /// repo 밖이면 None
+    pub git: Option<GitRepo>,
+    /// 설정이 깨져도 config 명령으로 고칠 수 있도록 오류를 그대로 둔다
+    pub config: Result<Config, String>,
+}
+
+impl Workspace {
+    /// 현재 디렉터리 기준으로 찾는다
+    pub fn discover() -> Result<Self, String> {
+        let cwd = std::env::current_dir().map_err(|e| tr!("app.cwd_failed", error = e))?;
+        Ok(Self::discover_from(&cwd))
+    }
+
+    pub fn discover_from(dir: &Path) -> Self {
+        let git = GitRepo::discover(dir).ok();
+        let root = match &git {
+            Some(git) => git.root(),
+            None => dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf()),
+        };
+        let config = Config::load(&root);
+        Self { root, git, config }
+    }
+}
+
+fn register_repo(root: &Path, log_dir: &Path) -> Result<(), String> {
+    Registry::load(&data_dir()?)?.register(root, log_dir)
+}
+
+/// 디렉터리별 임시 로그북은 절대 경로를 이름으로 쓴다
+pub fn scratch_name(config: &Config, dir: &Path) -> String {
+    if config.storage.scratch_per_directory {
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        dir.display().to_string()
+    } else {
+        GLOBAL_SCRATCH.to_string()
+    }
+}
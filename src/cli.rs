@@ -1,9 +1,52 @@
-use clap::{Parser, Subcommand};
+use clap::{Command, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::engine::ArgValueCandidates;
 
+use crate::complete;
+use crate::i18n::{self, tr};
+
+/// 도움말을 현재 언어 카탈로그(`help.*`)로 바꾼 clap 명령 정의
+pub fn command() -> Command {
+    localize(Cli::command(), "help")
+}
+
+fn localize(mut cmd: Command, prefix: &str) -> Command {
+    if let Some(about) = i18n::lookup(&format!("{}.about", prefix)) {
+        cmd = cmd.about(about);
+    }
+    let args: Vec<String> = cmd
+        .get_arguments()
+        .map(|arg| arg.get_id().to_string())
+        .collect();
+    for id in args {
+        if let Some(help) = i18n::lookup(&format!("{}.arg.{}", prefix, id)) {
+            cmd = cmd.mut_arg(id, |arg| arg.help(help));
+        }
+    }
+    let subcommands: Vec<String> = cmd
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect();
+    for name in subcommands {
+        let sub_prefix = format!("{}.{}", prefix, name);
+        cmd = cmd.mut_subcommand(name, |sub| localize(sub, &sub_prefix));
+    }
+    cmd
+}
+
+// 아래 doc 주석은 기본 도움말이고, 실제 출력은 `command()`에서 카탈로그 문구로 바뀐다.
 #[derive(Parser, Debug)]
 #[command(name = "bbiribarabu")]
 #[command(about = "브랜치 컨텍스트 로그 도구", long_about = None)]
 pub struct Cli {
+    /// 대상 브랜치 (기본값: 현재 브랜치)
+    #[arg(
+        short,
+        long,
+        global = true,
+        add = ArgValueCandidates::new(complete::branch_candidates)
+    )]
+    pub branch: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<Commands>, // 👈 Option으로 변경
 }
@@ -19,10 +62,160 @@ pub enum Commands {
     /// 현재 브랜치 로그 목록 조회
     List,
 
+    /// 현재 브랜치 로그 검색 (관련도순, 오타 허용)
+    Search {
+        /// 검색어
+        query: String,
+        /// 상위 N개만 출력
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+    },
+
+    /// 로그 텍스트 수정
+    Edit {
+        /// 수정할 로그 id
+        #[arg(add = ArgValueCandidates::new(complete::log_id_candidates))]
+        id: String,
+        /// 새 텍스트
+        text: String,
+    },
+
+    /// 로그 삭제
+    Rm {
+        /// 삭제할 로그 id
+        #[arg(add = ArgValueCandidates::new(complete::log_id_candidates))]
+        id: String,
+    },
+
+    /// 로그에 붙은 터미널 녹화 재생
+    Replay {
+        /// 재생할 로그 id
+        #[arg(add = ArgValueCandidates::new(complete::cast_id_candidates))]
+        id: String,
+        /// 재생 속도 배율
+        #[arg(short, long, default_value_t = 1.0, value_parser = positive_number)]
+        speed: f64,
+        /// 멈춰 있던 구간을 최대 N초로 줄임
+        #[arg(short, long, value_parser = non_negative_number)]
+        idle_limit: Option<f64>,
+    },
+
+    /// 임시 로그북의 노트를 브랜치로 옮기기
+    Adopt {
+        /// 옮길 임시 로그북 (디렉터리 경로 또는 이름, 기본값: 현재 디렉터리)
+        #[arg(long, add = ArgValueCandidates::new(complete::scratch_candidates))]
+        from: Option<String>,
+    },
+
+    /// 삭제된 브랜치의 로그북 정리 (기본: 압축 보관)
+    Gc {
+        /// 대상만 출력하고 아무것도 바꾸지 않음
+        #[arg(short = 'n', long, conflicts_with = "purge")]
+        dry_run: bool,
+        /// 보관하지 않고 완전히 삭제
+        #[arg(long)]
+        purge: bool,
+    },
+
+    /// 등록된 모든 repo의 로그 조회 (global.enabled로 등록)
+    Global {
+        #[command(subcommand)]
+        action: GlobalAction,
+    },
+
+    /// 쉘 프롬프트용 요약 출력 (할 일 수, 마지막 로그 이후 시간)
+    Prompt {
+        /// 출력 형식 ({branch}, {count}, {todos}, {age} 치환)
+        #[arg(short, long)]
+        format: Option<String>,
+    },
+
     /// 음성 인식 후 로그 추가
     Voice {
         /// 녹음 시간(초)
         #[arg(short, long, default_value_t = 5)]
         seconds: u64,
     },
+
+    /// 설정 조회/변경
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// 쉘 자동완성 스크립트 출력
+    Completions {
+        /// 대상 쉘
+        shell: CompletionShell,
+    },
+
+    /// man 페이지 출력 (roff)
+    Man,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// 설정 값 조회
+    Get {
+        /// 설정 키 (예: voice.language)
+        key: String,
+    },
+
+    /// 설정 값 저장 (기본: 레포 설정 파일)
+    Set {
+        /// 설정 키 (예: voice.language)
+        key: String,
+        /// 저장할 값
+        value: String,
+        /// 사용자 설정(~/.config/bbiribarabu/config.toml)에 저장
+        #[arg(long)]
+        global: bool,
+    },
+
+    /// 전체 설정과 출처 조회
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum GlobalAction {
+    /// 모든 repo/브랜치의 로그 목록 (시간순)
+    List {
+        /// 최근 N개만 출력
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+    },
+
+    /// 모든 repo/브랜치에서 로그 검색
+    Search {
+        /// 검색어 (관련도순, 한글 부분 입력/오타 허용)
+        query: String,
+    },
+
+    /// 등록된 repo 목록
+    Repos,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// `--speed`: 0보다 큰 유한한 수
+fn positive_number(value: &str) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite() && *number > 0.0)
+        .ok_or_else(|| tr!("cli.not_positive"))
+}
+
+/// `--idle-limit`: 0 이상의 유한한 수
+fn non_negative_number(value: &str) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite() && *number >= 0.0)
+        .ok_or_else(|| tr!("cli.negative"))
 }
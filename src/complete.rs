@@ -0,0 +1,168 @@
+use std::ffi::OsString;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use clap_complete::CompleteEnv;
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::env::{Bash, EnvCompleter, Fish, Zsh};
+
+use crate::app::AppState;
+use crate::cli::{self, CompletionShell};
+use crate::log::model::LogItem;
+
+/// 쉘이 자동완성 요청 시 설정하는 환경 변수
+const COMPLETE_VAR: &str = "COMPLETE";
+const BIN_NAME: &str = "bbiribarabu";
+
+/// `COMPLETE=<shell>`로 호출된 경우 후보를 출력하고 종료한다.
+/// stdout에 아무것도 쓰기 전에 호출해야 한다.
+pub fn handle_env() {
+    CompleteEnv::with_factory(cli::command)
+        .var(COMPLETE_VAR)
+        .complete();
+}
+
+/// `completions <shell>` 서브커맨드: 동적 자동완성 등록 스크립트 출력
+pub fn write_registration(shell: CompletionShell, out: &mut dyn Write) -> io::Result<()> {
+    let completer: &dyn EnvCompleter = match shell {
+        CompletionShell::Bash => &Bash,
+        CompletionShell::Zsh => &Zsh,
+        CompletionShell::Fish => &Fish,
+    };
+
+    // 스크립트는 실행 중인 바이너리를 다시 호출해 후보를 받아온다.
+    let exe = std::env::current_exe()
+        .map(PathBuf::into_os_string)
+        .unwrap_or_else(|_| OsString::from(BIN_NAME));
+    let bin = std::env::args_os()
+        .next()
+        .and_then(|arg0| {
+            PathBuf::from(arg0)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| BIN_NAME.to_string());
+
+    completer.write_registration(COMPLETE_VAR, BIN_NAME, &bin, &exe.to_string_lossy(), out)
+}
+
+/// `man` 서브커맨드: roff 형식 man 페이지 출력
+pub fn write_man(out: &mut dyn Write) -> io::Result<()> {
+    clap_mangen::Man::new(cli::command()).render(out)
+}
+
+/// 로그북이 있는 브랜치와 로컬 git 브랜치 이름 후보
+pub fn branch_candidates() -> Vec<CompletionCandidate> {
+    let Ok(app) = AppState::init() else {
+        return vec![];
+    };
+    let mut branches = app.log_store.branches().unwrap_or_default();
+    if let Some(git) = &app.git {
+        branches.extend(git.branches().unwrap_or_default());
+    }
+    branches.sort();
+    branches.dedup();
+    branches.into_iter().map(CompletionCandidate::new).collect()
+}
+
+/// 임시 로그북 이름 후보
+pub fn scratch_candidates() -> Vec<CompletionCandidate> {
+    let Ok(app) = AppState::init() else {
+        return vec![];
+    };
+    app.scratch_store()
+        .and_then(|store| store.branches())
+        .unwrap_or_default()
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// 현재 브랜치 로그 id 후보 (텍스트를 도움말로 표시)
+pub fn log_id_candidates() -> Vec<CompletionCandidate> {
+    id_candidates(|_| true)
+}
+
+/// 터미널 녹화가 붙은 로그 id 후보
+pub fn cast_id_candidates() -> Vec<CompletionCandidate> {
+    id_candidates(|item| item.cast.is_some())
+}
+
+fn id_candidates(filter: impl Fn(&LogItem) -> bool) -> Vec<CompletionCandidate> {
+    let Ok(mut app) = AppState::init() else {
+        return vec![];
+    };
+    // 명령줄에 --branch가 있으면 그 브랜치 로그에서 고른다
+    let words = std::env::args().skip_while(|arg| arg != "--").skip(1);
+    if let Some(branch) = branch_arg(words)
+        && app.select_branch(branch).is_err()
+    {
+        return vec![];
+    }
+    app.log_store
+        .list(&app.current_branch)
+        .unwrap_or_default()
+        .into_iter()
+        .rev()
+        .filter(|item| filter(item))
+        .map(|item| {
+            let summary = item.text.lines().next().unwrap_or_default();
+            let help = summary.chars().take(60).collect::<String>();
+            CompletionCandidate::new(item.id).help(Some(help.into()))
+        })
+        .collect()
+}
+
+/// 자동완성 중인 명령줄에서 `-b`/`--branch` 값 (여러 번이면 마지막 값)
+fn branch_arg(words: impl Iterator<Item = String>) -> Option<String> {
+    let mut branch = None;
+    let mut words = words.peekable();
+    while let Some(word) = words.next() {
+        if word == "--" {
+            break;
+        }
+        if word == "-b" || word == "--branch" {
+            // 아직 값을 입력하는 중이면 마지막 단어가 빈 문자열로 온다
+            if let Some(value) = words.next().filter(|value| !value.is_empty()) {
+                branch = Some(value);
+            }
+        } else if let Some(value) = word.strip_prefix("--branch=") {
+            branch = Some(value.to_string());
+        } else if let Some(value) = word.strip_prefix("-b")
+            && !value.is_empty()
+        {
+            branch = Some(value.to_string());
+        }
+    }
+    branch
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(line: &str) -> impl Iterator<Item = String> {
+        line.split(' ')
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn reads_branch_from_partial_command_line() {
+        assert_eq!(branch_arg(words("bbiribarabu rm ")), None);
+        assert_eq!(
+            branch_arg(words("bbiribarabu --branch other rm ")).as_deref(),
+            Some("other")
+        );
+        assert_eq!(
+            branch_arg(words("bbiribarabu rm -b feat/x ")).as_deref(),
+            Some("feat/x")
+        );
+        assert_eq!(
+            branch_arg(words("bbiribarabu --branch=a -bb edit ")).as_deref(),
+            Some("b")
+        );
+        assert_eq!(branch_arg(words("bbiribarabu rm --branch ")), None);
+    }
+}
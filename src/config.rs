@@ -0,0 +1,646 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+
+use crate::i18n::{Locale, tr};
+use crate::ui::keymap::KeyBinding;
+use crate::ui::layout::Preset;
+use crate::voice::VadConfig;
+
+const CONFIG_FILENAME: &str = "config.toml";
+const ENV_PREFIX: &str = "BBIRIBARABU_";
+
+pub const DEFAULT_MODEL_URL: &str =
+    "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin";
+
+/// 기본값 < 사용자 설정 < 레포 설정 < 환경 변수 순으로 병합된 최종 설정
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub storage: StorageConfig,
+    pub voice: VoiceConfig,
+    pub git: GitConfig,
+    pub keys: KeysConfig,
+    pub ui: UiConfig,
+    pub global: GlobalConfig,
+    pub shell: ShellConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// 로그 디렉터리 (상대 경로면 repo root 기준)
+    pub dir: String,
+    /// git repo 밖이나 브랜치가 없을 때 쓰는 임시 로그 디렉터리 (비어 있으면 ~/.local/share/bbiribarabu/scratch)
+    pub scratch_dir: String,
+    /// true면 디렉터리별로, false면 하나의 공용 임시 로그북에 기록
+    pub scratch_per_directory: bool,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            dir: ".bbiribarabu/logs".to_string(),
+            scratch_dir: String::new(),
+            scratch_per_directory: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VoiceConfig {
+    /// Whisper 인식 언어 코드
+    pub language: String,
+    pub model_url: String,
+    pub vad: VadConfig,
+}
+
+impl Default for VoiceConfig {
+    fn default() -> Self {
+        Self {
+            language: "ko".to_string(),
+            model_url: DEFAULT_MODEL_URL.to_string(),
+            vad: VadConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitConfig {
+    /// 파일 감시를 시작하지 못했을 때 TUI에서 현재 브랜치를 다시 확인하는 주기
+    pub branch_poll_ms: u64,
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            branch_poll_ms: 500,
+        }
+    }
+}
+
+/// TUI 키 바인딩 (예: "i", "Esc", "F2", "Ctrl+b")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeysConfig {
+    pub toggle_focus: String,
+    pub add: String,
+    pub edit: String,
+    pub delete: String,
+    pub search: String,
+    pub voice: String,
+    pub quit: String,
+    pub debug: String,
+    /// 마지막 명령과 출력 끝부분을 로그로 저장 (shell.integration 필요)
+    pub log_command: String,
+    /// 터미널 출력 선택 모드 (선택한 부분을 로그에 코드 블록으로 첨부)
+    pub select: String,
+    /// 새 터미널 탭 (저장소 루트에서 쉘을 하나 더 띄움)
+    pub new_tab: String,
+    /// 이전/다음 터미널 탭으로 전환
+    pub prev_tab: String,
+    pub next_tab: String,
+    /// 현재 터미널 탭과 그 쉘을 닫음 (마지막 탭은 닫지 않음)
+    pub close_tab: String,
+    /// 쉘이 끝난 탭을 저장소 루트/마지막 디렉터리에서 다시 시작
+    pub restart_shell: String,
+    pub restart_shell_cwd: String,
+    /// 터미널 스크롤백 검색 (로그 검색과 별개)
+    pub term_search: String,
+    /// 현재 터미널 탭 녹화 시작/끝 (끝나면 설명을 받아 로그에 붙임)
+    pub record: String,
+    /// 포커스가 있는 패널 확대/복원. 배치 키(zoom, layout, grow, shrink)는 쉘의 줄 편집 키와
+    /// 겹치므로 리더가 없으면 터미널 포커스에서는 쉘로 가고 로그 패널에서만 동작한다.
+    pub zoom: String,
+    /// 배치 전환 (나란히 → 위아래 → 터미널만 → 로그만)
+    pub layout: String,
+    /// 터미널 패널 키우기/줄이기
+    pub grow: String,
+    pub shrink: String,
+    /// 터미널 포커스에서 단축키 앞에 누르는 키 (예: "Ctrl+b"). 비우면 단축키를 바로 받고,
+    /// 정하면 Esc를 포함한 나머지 키는 모두 쉘로 간다.
+    pub leader: String,
+}
+
+impl Default for KeysConfig {
+    fn default() -> Self {
+        Self {
+            toggle_focus: "Esc".to_string(),
+            add: "i".to_string(),
+            edit: "e".to_string(),
+            delete: "d".to_string(),
+            search: "/".to_string(),
+            voice: "v".to_string(),
+            quit: "q".to_string(),
+            debug: "F2".to_string(),
+            log_command: "F3".to_string(),
+            select: "F4".to_string(),
+            new_tab: "F5".to_string(),
+            prev_tab: "F6".to_string(),
+            next_tab: "F7".to_string(),
+            close_tab: "F8".to_string(),
+            restart_shell: "r".to_string(),
+            restart_shell_cwd: "c".to_string(),
+            term_search: "F9".to_string(),
+            record: "F10".to_string(),
+            zoom: "Alt+z".to_string(),
+            layout: "Alt+l".to_string(),
+            grow: "Alt+=".to_string(),
+            shrink: "Alt+-".to_string(),
+            leader: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiConfig {
+    /// 표시 언어 ("auto"면 LANG 등 환경 변수 기준, "en", "ko")
+    pub locale: String,
+    /// 브랜치 전환 시 요약 창에 보여 줄 최근 로그 수 (0이면 요약 창을 띄우지 않음)
+    pub recap_notes: usize,
+    /// 터미널과 로그 목록 배치 ("side", "stacked", "terminal", "logs")
+    pub layout: String,
+    /// 둘 다 보일 때 터미널 패널이 차지하는 비율(%, 20~80)
+    pub split: u16,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            locale: "auto".to_string(),
+            recap_notes: 3,
+            layout: "side".to_string(),
+            split: 60,
+        }
+    }
+}
+
+/// TUI 안의 쉘 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShellConfig {
+    /// true면 bash/zsh에 OSC 133 프롬프트 표시를 심어서 명령 경계와 종료 코드를 추적
+    pub integration: bool,
+    /// 명령을 로그로 저장할 때 남길 출력 줄 수
+    pub output_lines: usize,
+    /// 터미널에서 실행할 명령과 인자 (비어 있으면 $SHELL, 따옴표로 묶기 가능. 예: "tmux new -A -s work")
+    pub command: String,
+    /// 쉘에 추가로 넘길 환경 변수 (예: "RUST_LOG=debug EDITOR='code --wait'")
+    pub env: String,
+}
+
+impl Default for ShellConfig {
+    fn default() -> Self {
+        Self {
+            integration: false,
+            output_lines: 20,
+            command: String::new(),
+            env: String::new(),
+        }
+    }
+}
+
+/// 여러 repo의 로그를 모아 보는 전역 목록 (opt-in)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GlobalConfig {
+    /// true면 실행한 repo를 전역 목록에 등록
+    pub enabled: bool,
+}
+
+impl Config {
+    pub fn load(repo_root: &Path) -> Result<Self, String> {
+        ConfigLayers::load(repo_root)?.resolve()
+    }
+
+    pub fn log_dir(&self, repo_root: &Path) -> PathBuf {
+        let dir = Path::new(&self.storage.dir);
+        if dir.is_absolute() {
+            dir.to_path_buf()
+        } else {
+            repo_root.join(dir)
+        }
+    }
+
+    pub fn scratch_dir(&self) -> Result<PathBuf, String> {
+        if !self.storage.scratch_dir.is_empty() {
+            return Ok(PathBuf::from(&self.storage.scratch_dir));
+        }
+        Ok(data_dir()?.join("scratch"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigScope {
+    User,
+    Repo,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    Default,
+    User,
+    Repo,
+    Env,
+}
+
+impl ConfigSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            ConfigSource::Default => "default",
+            ConfigSource::User => "user",
+            ConfigSource::Repo => "repo",
+            ConfigSource::Env => "env",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigEntry {
+    pub key: String,
+    pub value: Value,
+    pub source: ConfigSource,
+}
+
+/// 설정 파일 레이어. `config get/set/list`에서 출처를 보여주기 위해 병합 전 상태를 보관한다.
+#[derive(Debug)]
+pub struct ConfigLayers {
+    user_path: Option<PathBuf>,
+    repo_path: PathBuf,
+    user: Table,
+    repo: Table,
+}
+
+impl ConfigLayers {
+    pub fn load(repo_root: &Path) -> Result<Self, String> {
+        let user_path = user_config_path();
+        let repo_path = repo_config_path(repo_root);
+        let user = match &user_path {
+            Some(path) => read_table(path)?,
+            None => Table::new(),
+        };
+        let repo = read_table(&repo_path)?;
+
+        Ok(Self {
+            user_path,
+            repo_path,
+            user,
+            repo,
+        })
+    }
+
+    pub fn resolve(&self) -> Result<Config, String> {
+        let mut merged = Table::new();
+        for entry in self.entries()? {
+            insert_dotted(&mut merged, &entry.key, entry.value);
+        }
+        Value::Table(merged)
+            .try_into()
+            .map_err(|e| tr!("config.invalid", error = e))
+    }
+
+    /// 알려진 모든 키의 최종 값과 출처
+    pub fn entries(&self) -> Result<Vec<ConfigEntry>, String> {
+        self.entries_with_env(|name| std::env::var(name).ok())
+    }
+
+    fn entries_with_env(
+        &self,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Vec<ConfigEntry>, String> {
+        let mut entries = Vec::new();
+        for (key, default) in default_entries() {
+            let mut value = default.clone();
+            let mut source = ConfigSource::Default;
+
+            for (layer, layer_source) in [
+                (&self.user, ConfigSource::User),
+                (&self.repo, ConfigSource::Repo),
+            ] {
+                if let Some(found) = lookup_dotted(layer, &key) {
+                    value = coerce_value(&key, found, &default)?;
+                    source = layer_source;
+                }
+            }
+
+            if let Some(raw) = env(&env_var_name(&key)) {
+                value = parse_value(&key, &raw, &default)?;
+                source = ConfigSource::Env;
+            }
+
+            entries.push(ConfigEntry { key, value, source });
+        }
+        Ok(entries)
+    }
+
+    pub fn get(&self, key: &str) -> Result<ConfigEntry, String> {
+        self.entries()?
+            .into_iter()
+            .find(|entry| entry.key == key)
+            .ok_or_else(|| tr!("config.unknown_key", key = key))
+    }
+
+    /// 값을 검증한 뒤 지정한 레이어 파일에 기록하고 그 경로를 돌려준다.
+    pub fn set(&mut self, scope: ConfigScope, key: &str, raw: &str) -> Result<PathBuf, String> {
+        let default = default_entries()
+            .into_iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+            .ok_or_else(|| tr!("config.unknown_key", key = key))?;
+        let value = parse_value(key, raw, &default)?;
+        if key.starts_with("keys.") && !(key == "keys.leader" && raw.trim().is_empty()) {
+            KeyBinding::parse(raw).map_err(|e| tr!("config.invalid_key", key = key, error = e))?;
+        }
+        if key == "ui.locale" && Locale::from_setting(raw).is_none() {
+            return Err(tr!("config.invalid_locale", key = key, value = raw));
+        }
+        if key == "ui.layout" && Preset::from_setting(raw).is_none() {
+            return Err(tr!("config.invalid_layout", key = key, value = raw));
+        }
+
+        let (table, path) = match scope {
+            ConfigScope::User => (
+                &mut self.user,
+                self.user_path
+                    .clone()
+                    .ok_or_else(|| tr!("config.no_user_dir"))?,
+            ),
+            ConfigScope::Repo => (&mut self.repo, self.repo_path.clone()),
+        };
+        insert_dotted(table, key, value);
+        // 기록 전에 전체 설정이 여전히 유효한지 확인
+        self.resolve()?;
+
+        let table = match scope {
+            ConfigScope::User => &self.user,
+            ConfigScope::Repo => &self.repo,
+        };
+        write_table(&path, table)?;
+        Ok(path)
+    }
+}
+
+pub fn user_config_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("bbiribarabu").join(CONFIG_FILENAME))
+}
+
+/// 임시 로그북과 전역 repo 목록 위치 ($XDG_DATA_HOME 또는 ~/.local/share 아래)
+pub fn data_dir() -> Result<PathBuf, String> {
+    let base = std::env::var_os("XDG_DATA_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .ok_or_else(|| tr!("config.no_data_dir"))?;
+    Ok(base.join("bbiribarabu"))
+}
+
+pub fn repo_config_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(".bbiribarabu").join(CONFIG_FILENAME)
+}
+
+/// `voice.vad.max_record_ms` → `BBIRIBARABU_VOICE_VAD_MAX_RECORD_MS`
+pub fn env_var_name(key: &str) -> String {
+    format!("{}{}", ENV_PREFIX, key.replace('.', "_").to_uppercase())
+}
+
+/// 값 표시용 (문자열은 따옴표 없이)
+pub fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        // 실수 설정은 모두 f32라서 f32 정밀도로 보여준다 (0.019999… 대신 0.02)
+        Value::Float(f) => (*f as f32).to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn default_entries() -> Vec<(String, Value)> {
+    let value = Value::try_from(Config::default()).expect("default config serializes");
+    let mut out = Vec::new();
+    flatten("", &value, &mut out);
+    out
+}
+
+fn flatten(prefix: &str, value: &Value, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Table(table) => {
+            for (k, v) in table {
+                let key = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{}.{}", prefix, k)
+                };
+                flatten(&key, v, out);
+            }
+        }
+        other => out.push((prefix.to_string(), other.clone())),
+    }
+}
+
+fn lookup_dotted<'a>(table: &'a Table, key: &str) -> Option<&'a Value> {
+    let mut parts = key.split('.').peekable();
+    let mut current = table;
+    while let Some(part) = parts.next() {
+        let value = current.get(part)?;
+        if parts.peek().is_none() {
+            return Some(value);
+        }
+        current = value.as_table()?;
+    }
+    None
+}
+
+fn insert_dotted(table: &mut Table, key: &str, value: Value) {
+    let mut parts = key.split('.').collect::<Vec<_>>();
+    let last = parts.pop().unwrap_or_default();
+    let mut current = table;
+    for part in parts {
+        let entry = current
+            .entry(part.to_string())
+            .or_insert_with(|| Value::Table(Table::new()));
+        if !entry.is_table() {
+            *entry = Value::Table(Table::new());
+        }
+        current = entry.as_table_mut().expect("just ensured table");
+    }
+    current.insert(last.to_string(), value);
+}
+
+/// 파일에서 읽은 값을 기본값 타입에 맞춘다 (정수 → 실수 허용)
+fn coerce_value(key: &str, found: &Value, default: &Value) -> Result<Value, String> {
+    match (default, found) {
+        (Value::Float(_), Value::Integer(i)) => Ok(Value::Float(*i as f64)),
+        (d, f) if d.same_type(f) => Ok(f.clone()),
+        _ => Err(tr!(
+            "config.type_mismatch",
+            key = key,
+            expected = default.type_str()
+        )),
+    }
+}
+
+fn parse_value(key: &str, raw: &str, default: &Value) -> Result<Value, String> {
+    let invalid = || {
+        tr!(
+            "config.invalid_value",
+            key = key,
+            value = raw,
+            expected = default.type_str()
+        )
+    };
+    match default {
+        Value::String(_) => Ok(Value::String(raw.to_string())),
+        Value::Integer(_) => raw
+            .trim()
+            .parse()
+            .map(Value::Integer)
+            .map_err(|_| invalid()),
+        Value::Float(_) => raw.trim().parse().map(Value::Float).map_err(|_| invalid()),
+        Value::Boolean(_) => raw
+            .trim()
+            .parse()
+            .map(Value::Boolean)
+            .map_err(|_| invalid()),
+        _ => Err(invalid()),
+    }
+}
+
+fn read_table(path: &Path) -> Result<Table, String> {
+    if !path.exists() {
+        return Ok(Table::new());
+    }
+    let data = fs::read_to_string(path)
+        .map_err(|e| tr!("config.read_failed", error = e, path = path.display()))?;
+    data.parse::<Table>()
+        .map_err(|e| tr!("config.parse_failed", error = e, path = path.display()))
+}
+
+fn write_table(path: &Path, table: &Table) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| tr!("config.dir_create_failed", error = e))?;
+    }
+    let data =
+        toml::to_string_pretty(table).map_err(|e| tr!("config.serialize_failed", error = e))?;
+    fs::write(path, data).map_err(|e| tr!("config.write_failed", error = e, path = path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layers(dir: &Path, user: &str, repo: &str) -> ConfigLayers {
+        ConfigLayers {
+            user_path: Some(dir.join("user.toml")),
+            repo_path: dir.join("repo.toml"),
+            user: user.parse().unwrap(),
+            repo: repo.parse().unwrap(),
+        }
+    }
+
+    fn entry(entries: &[ConfigEntry], key: &str) -> (Value, ConfigSource) {
+        let entry = entries.iter().find(|entry| entry.key == key).unwrap();
+        (entry.value.clone(), entry.source)
+    }
+
+    #[test]
+    fn layers_override_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let layers = layers(
+            dir.path(),
+            "[git]\nbranch_poll_ms = 100\n[ui]\nrecap_notes = 7\nlocale = \"ko\"\n",
+            "[git]\nbranch_poll_ms = 200\n[voice.vad]\nstart_threshold = 1\n",
+        );
+        let entries = layers
+            .entries_with_env(|name| (name == "BBIRIBARABU_UI_LOCALE").then(|| "en".to_string()))
+            .unwrap();
+
+        assert_eq!(
+            entry(&entries, "shell.integration"),
+            (Value::Boolean(false), ConfigSource::Default)
+        );
+        assert_eq!(
+            entry(&entries, "ui.recap_notes"),
+            (Value::Integer(7), ConfigSource::User)
+        );
+        assert_eq!(
+            entry(&entries, "git.branch_poll_ms"),
+            (Value::Integer(200), ConfigSource::Repo)
+        );
+        assert_eq!(
+            entry(&entries, "ui.locale"),
+            (Value::String("en".to_string()), ConfigSource::Env)
+        );
+        // 실수 설정에 정수를 적어도 된다
+        assert_eq!(
+            entry(&entries, "voice.vad.start_threshold"),
+            (Value::Float(1.0), ConfigSource::Repo)
+        );
+    }
+
+    #[test]
+    fn bad_types_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let wrong_type = layers(dir.path(), "[git]\nbranch_poll_ms = \"fast\"\n", "");
+        assert!(wrong_type.entries_with_env(|_| None).is_err());
+
+        let empty = layers(dir.path(), "", "");
+        let env = |name: &str| (name == "BBIRIBARABU_SHELL_INTEGRATION").then(|| "yes".into());
+        assert!(empty.entries_with_env(env).is_err());
+    }
+
+    #[test]
+    fn set_coerces_validates_and_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut layers = layers(dir.path(), "", "");
+
+        let path = layers
+            .set(ConfigScope::Repo, "ui.recap_notes", " 3 ")
+            .unwrap();
+        assert_eq!(path, dir.path().join("repo.toml"));
+        let written = read_table(&path).unwrap();
+        assert_eq!(
+            lookup_dotted(&written, "ui.recap_notes"),
+            Some(&Value::Integer(3))
+        );
+
+        layers
+            .set(ConfigScope::User, "shell.integration", "true")
+            .unwrap();
+        let written = read_table(&dir.path().join("user.toml")).unwrap();
+        assert_eq!(
+            lookup_dotted(&written, "shell.integration"),
+            Some(&Value::Boolean(true))
+        );
+
+        assert!(
+            layers
+                .set(ConfigScope::User, "ui.recap_notes", "many")
+                .is_err()
+        );
+        assert!(
+            layers
+                .set(ConfigScope::User, "shell.integration", "1")
+                .is_err()
+        );
+        assert!(layers.set(ConfigScope::User, "no.such_key", "1").is_err());
+        assert!(
+            layers
+                .set(ConfigScope::User, "keys.quit", "Hyper+q")
+                .is_err()
+        );
+        assert!(layers.set(ConfigScope::User, "ui.locale", "fr").is_err());
+        assert!(layers.set(ConfigScope::User, "ui.layout", "grid").is_err());
+        assert!(layers.set(ConfigScope::User, "keys.leader", "").is_ok());
+        // 실패한 값은 기록되지 않는다
+        let written = read_table(&dir.path().join("user.toml")).unwrap();
+        assert_eq!(lookup_dotted(&written, "ui.locale"), None);
+    }
+}
@@ -1,23 +1,84 @@
-use std::process::Command;
+use std::collections::HashSet;
 
-pub fn current_branch() -> Result<String, String> {
-    let output = Command::new("git")
-        .args(["branch", "--show-current"])
-        .output()
-        .map_err(|e| format!("git 실행 실패: {}", e))?;
+use git2::BranchType;
 
-    if !output.status.success() {
-        return Err("git 명령이 정상 종료되지 않음".to_string());
+use crate::git::repo::{GitRepo, git_error};
+
+impl GitRepo {
+    /// 로컬 브랜치 목록 (이름순)
+    pub fn branches(&self) -> Result<Vec<String>, String> {
+        let mut names = self.branch_names(BranchType::Local)?;
+        names.sort();
+        Ok(names)
+    }
+
+    /// 원격 추적 브랜치 이름에서 원격 이름을 뗀 목록 (`origin/feature/x` → `feature/x`)
+    pub fn remote_branches(&self) -> Result<Vec<String>, String> {
+        let remotes = self.inner().remotes().map_err(git_error)?;
+        let prefixes = remotes
+            .iter()
+            .filter_map(|remote| remote.ok().flatten())
+            .map(|remote| format!("{remote}/"))
+            .collect::<Vec<_>>();
+        let mut names = self
+            .branch_names(BranchType::Remote)?
+            .into_iter()
+            .filter_map(|name| {
+                prefixes
+                    .iter()
+                    .find_map(|prefix| name.strip_prefix(prefix.as_str()))
+                    .filter(|short| *short != "HEAD")
+                    .map(str::to_string)
+            })
+            .collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    /// 로컬이나 원격 어디에든 남아 있는 브랜치 (아직 커밋 없는 현재 브랜치 포함)
+    pub fn live_branches(&self) -> Result<HashSet<String>, String> {
+        let mut live = self.branches()?.into_iter().collect::<HashSet<_>>();
+        live.extend(self.remote_branches()?);
+        if let Ok(current) = self.current_branch() {
+            live.insert(current);
+        }
+        Ok(live)
+    }
+
+    fn branch_names(&self, kind: BranchType) -> Result<Vec<String>, String> {
+        let mut names = Vec::new();
+        for branch in self.inner().branches(Some(kind)).map_err(git_error)? {
+            let (branch, _) = branch.map_err(git_error)?;
+            if let Some(name) = branch.name().map_err(git_error)? {
+                names.push(name.to_string());
+            }
+        }
+        Ok(names)
     }
 
-    let branch = String::from_utf8(output.stdout)
-        .map_err(|_| "브랜치 이름을 문자열로 변환 실패".to_string())?
-        .trim()
-        .to_string();
+    /// `branch`의 끝 커밋이 `into`에 이미 포함되어 있는지
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn is_merged(&self, branch: &str, into: &str) -> Result<bool, String> {
+        let tip = self.resolve(branch)?;
+        let base = self.resolve(into)?;
+        if tip == base {
+            return Ok(true);
+        }
+        self.inner()
+            .graph_descendant_of(base, tip)
+            .map_err(git_error)
+    }
 
-    if branch.is_empty() {
-        Err("현재 브랜치를 찾을 수 없음".to_string())
-    } else {
-        Ok(branch)
+    /// `into`에 합쳐진 로컬 브랜치 목록 (`into` 자신은 제외)
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn merged_branches(&self, into: &str) -> Result<Vec<String>, String> {
+        let mut merged = Vec::new();
+        for branch in self.branches()? {
+            if branch != into && self.is_merged(&branch, into)? {
+                merged.push(branch);
+            }
+        }
+        Ok(merged)
     }
 }
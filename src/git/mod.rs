@@ -1,2 +1,4 @@
 pub mod branch;
 pub mod repo;
+#[cfg(test)]
+mod tests;
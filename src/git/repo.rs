@@ -1,24 +1,175 @@
-use std::path::PathBuf;
-use std::process::Command;
+use std::fmt;
+use std::path::{Path, PathBuf};
 
-pub fn repo_root() -> Result<PathBuf, String> {
-    let output = Command::new("git")
-        .args(["rev-parse", "--show-toplevel"])
-        .output()
-        .map_err(|e| format!("git 실행 실패: {}", e))?;
+use chrono::{DateTime, Local, TimeZone};
+use git2::{Oid, Repository};
 
-    if !output.status.success() {
-        return Err("git repo가 아님 (rev-parse 실패)".to_string());
+use crate::i18n::tr;
+
+/// 프로세스 안에서 여는 git 저장소 (git 바이너리 없이 동작)
+pub struct GitRepo {
+    repo: Repository,
+}
+
+/// HEAD가 가리키는 상태
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeadState {
+    /// 커밋이 있는 브랜치
+    Branch(String),
+    /// 아직 커밋이 없는 브랜치 (git init 직후)
+    Unborn(String),
+    /// 브랜치 없이 커밋을 가리킴
+    Detached(String),
+}
+
+impl HeadState {
+    /// 로그북에 쓸 브랜치 이름 (detached면 None)
+    pub fn branch(&self) -> Option<&str> {
+        match self {
+            Self::Branch(name) | Self::Unborn(name) => Some(name),
+            Self::Detached(_) => None,
+        }
+    }
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitInfo {
+    pub id: String,
+    pub summary: String,
+    pub author: String,
+    pub time: DateTime<Local>,
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReflogEntry {
+    /// 이전 커밋 id (새로 만든 ref면 0으로 채운 id)
+    pub old_id: String,
+    pub new_id: String,
+    pub message: String,
+    pub time: DateTime<Local>,
+}
+
+impl fmt::Debug for GitRepo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GitRepo")
+            .field("git_dir", &self.repo.path())
+            .finish()
+    }
+}
+
+impl GitRepo {
+    /// `path`에서 위쪽으로 올라가며 저장소를 찾는다
+    pub fn discover(path: &Path) -> Result<Self, String> {
+        let repo = Repository::discover(path).map_err(|_| tr!("git.not_repo"))?;
+        if repo.is_bare() {
+            return Err(tr!("git.bare_repo"));
+        }
+        Ok(Self { repo })
+    }
+
+    pub(super) fn inner(&self) -> &Repository {
+        &self.repo
+    }
+
+    /// 작업 트리 root
+    pub fn root(&self) -> PathBuf {
+        let workdir = self.repo.workdir().unwrap_or_else(|| self.repo.path());
+        let root = workdir.to_path_buf();
+        root.canonicalize().unwrap_or(root)
+    }
+
+    /// .git 디렉터리 (worktree면 그 worktree의 git 디렉터리)
+    pub fn git_dir(&self) -> &Path {
+        self.repo.path()
+    }
+
+    /// HEAD를 매번 파일에서 다시 읽는다
+    pub fn head(&self) -> Result<HeadState, String> {
+        if self.repo.head_detached().map_err(git_error)? {
+            let id = self.repo.head().map_err(git_error)?.target();
+            return Ok(HeadState::Detached(
+                id.map(|id| id.to_string()).unwrap_or_default(),
+            ));
+        }
+        let head = self.repo.find_reference("HEAD").map_err(git_error)?;
+        let target = head
+            .symbolic_target()
+            .map_err(git_error)?
+            .ok_or_else(|| tr!("git.no_branch"))?;
+        let name = target
+            .strip_prefix("refs/heads/")
+            .unwrap_or(target)
+            .to_string();
+        // HEAD가 가리키는 브랜치 ref가 아직 없으면 첫 커밋 전
+        if self.repo.refname_to_id(target).is_err() {
+            Ok(HeadState::Unborn(name))
+        } else {
+            Ok(HeadState::Branch(name))
+        }
+    }
+
+    /// 현재 브랜치 이름 (detached면 에러)
+    pub fn current_branch(&self) -> Result<String, String> {
+        self.head()?
+            .branch()
+            .map(str::to_string)
+            .ok_or_else(|| tr!("git.no_branch"))
+    }
+
+    /// 리비전(브랜치, 태그, 커밋 id, `HEAD~1` 등)이 가리키는 커밋
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn commit(&self, rev: &str) -> Result<CommitInfo, String> {
+        let commit = self
+            .repo
+            .revparse_single(rev)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|e| tr!("git.rev_not_found", rev = rev, error = e.message()))?;
+        let author = commit.author();
+        Ok(CommitInfo {
+            id: commit.id().to_string(),
+            summary: String::from_utf8_lossy(commit.summary_bytes().unwrap_or_default())
+                .into_owned(),
+            author: String::from_utf8_lossy(author.name_bytes()).into_owned(),
+            time: local_time(commit.time().seconds()),
+        })
     }
 
-    let s = String::from_utf8(output.stdout)
-        .map_err(|_| "repo root 문자열 변환 실패".to_string())?
-        .trim()
-        .to_string();
+    /// ref의 reflog, 최신 항목부터 (`HEAD`, `refs/heads/main` 등)
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn reflog(&self, refname: &str) -> Result<Vec<ReflogEntry>, String> {
+        let reflog = self.repo.reflog(refname).map_err(git_error)?;
+        Ok(reflog
+            .iter()
+            .map(|entry| ReflogEntry {
+                old_id: entry.id_old().to_string(),
+                new_id: entry.id_new().to_string(),
+                message: String::from_utf8_lossy(entry.message_bytes().unwrap_or_default())
+                    .into_owned(),
+                time: local_time(entry.committer().when().seconds()),
+            })
+            .collect())
+    }
 
-    if s.is_empty() {
-        Err("repo root 경로를 찾지 못함".to_string())
-    } else {
-        Ok(PathBuf::from(s))
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(super) fn resolve(&self, rev: &str) -> Result<Oid, String> {
+        self.repo
+            .revparse_single(rev)
+            .and_then(|object| object.peel_to_commit())
+            .map(|commit| commit.id())
+            .map_err(|e| tr!("git.rev_not_found", rev = rev, error = e.message()))
     }
 }
+
+pub(super) fn git_error(e: git2::Error) -> String {
+    tr!("git.failed", error = e.message())
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+fn local_time(seconds: i64) -> DateTime<Local> {
+    Local
+        .timestamp_opt(seconds, 0)
+        .single()
+        .unwrap_or_else(Local::now)
+}
@@ -0,0 +1,202 @@
+//! git 바이너리 없이 git2로 만든 임시 저장소에서 `GitRepo`를 확인한다.
+
+use std::path::Path;
+
+use git2::{Oid, Repository, Signature, Time};
+use tempfile::TempDir;
+
+use crate::git::repo::{GitRepo, HeadState};
+
+struct Fixture {
+    dir: TempDir,
+    repo: Repository,
+}
+
+impl Fixture {
+    /// `main`이 HEAD인 빈 저장소
+    fn new() -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+        Self { dir, repo }
+    }
+
+    fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    fn open(&self) -> GitRepo {
+        GitRepo::discover(self.path()).unwrap()
+    }
+
+    /// 현재 HEAD 위에 빈 트리 커밋을 만든다
+    fn commit(&self, message: &str, seconds: i64) -> Oid {
+        let sig = Signature::new("Tester", "tester@example.com", &Time::new(seconds, 0)).unwrap();
+        let tree_id = self.repo.treebuilder(None).unwrap().write().unwrap();
+        let tree = self.repo.find_tree(tree_id).unwrap();
+        let parent = self
+            .repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok());
+        let parents = parent.iter().collect::<Vec<_>>();
+        self.repo
+            .commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+
+    fn checkout_new(&self, branch: &str) {
+        let head = self.repo.head().unwrap().peel_to_commit().unwrap();
+        self.repo.branch(branch, &head, false).unwrap();
+        self.switch(branch);
+    }
+
+    fn switch(&self, branch: &str) {
+        self.repo.set_head(&format!("refs/heads/{branch}")).unwrap();
+    }
+}
+
+#[test]
+fn head_of_fresh_repo_is_unborn_branch() {
+    let fx = Fixture::new();
+    let git = fx.open();
+    assert_eq!(git.head().unwrap(), HeadState::Unborn("main".to_string()));
+    assert_eq!(git.current_branch().unwrap(), "main");
+    assert!(git.branches().unwrap().is_empty());
+}
+
+#[test]
+fn head_follows_branch_switches_without_reopening() {
+    let fx = Fixture::new();
+    fx.commit("init", 1_700_000_000);
+    let git = fx.open();
+    assert_eq!(git.head().unwrap(), HeadState::Branch("main".to_string()));
+
+    fx.checkout_new("feature/login");
+    assert_eq!(git.current_branch().unwrap(), "feature/login");
+    assert_eq!(git.branches().unwrap(), ["feature/login", "main"]);
+}
+
+#[test]
+fn detached_head_has_no_branch() {
+    let fx = Fixture::new();
+    let id = fx.commit("init", 1_700_000_000);
+    fx.repo.set_head_detached(id).unwrap();
+
+    let git = fx.open();
+    assert_eq!(git.head().unwrap(), HeadState::Detached(id.to_string()));
+    assert!(git.current_branch().is_err());
+}
+
+#[test]
+fn discovers_root_from_subdirectory() {
+    let fx = Fixture::new();
+    let nested = fx.path().join("src/deep");
+    std::fs::create_dir_all(&nested).unwrap();
+
+    let git = GitRepo::discover(&nested).unwrap();
+    assert_eq!(git.root(), fx.path().canonicalize().unwrap());
+    assert!(git.git_dir().ends_with(".git/"));
+}
+
+#[test]
+fn discover_outside_repo_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    assert!(GitRepo::discover(dir.path()).is_err());
+}
+
+#[test]
+fn merged_branches_are_ancestors_of_target() {
+    let fx = Fixture::new();
+    fx.commit("init", 1_700_000_000);
+    fx.checkout_new("done");
+    fx.commit("finished work", 1_700_000_100);
+    fx.checkout_new("wip");
+    fx.commit("half done", 1_700_000_200);
+
+    // main을 done까지 fast-forward
+    let done = fx.repo.revparse_single("done").unwrap().id();
+    fx.repo
+        .reference("refs/heads/main", done, true, "fast-forward")
+        .unwrap();
+    // 새로 만든 뒤 커밋이 없는 브랜치도 합쳐진 것으로 본다
+    fx.switch("main");
+    fx.checkout_new("fresh");
+    fx.switch("main");
+
+    let git = fx.open();
+    assert!(git.is_merged("done", "main").unwrap());
+    assert!(!git.is_merged("wip", "main").unwrap());
+    assert!(git.is_merged("main", "wip").unwrap());
+    assert_eq!(git.merged_branches("main").unwrap(), ["done", "fresh"]);
+    assert!(git.is_merged("missing", "main").is_err());
+}
+
+#[test]
+fn commit_lookup_by_revision() {
+    let fx = Fixture::new();
+    let first = fx.commit("first commit\n\nbody", 1_700_000_000);
+    let second = fx.commit("second commit", 1_700_000_060);
+
+    let git = fx.open();
+    let head = git.commit("HEAD").unwrap();
+    assert_eq!(head.id, second.to_string());
+    assert_eq!(head.summary, "second commit");
+    assert_eq!(head.author, "Tester");
+    assert_eq!(head.time.timestamp(), 1_700_000_060);
+
+    let parent = git.commit("HEAD~1").unwrap();
+    assert_eq!(parent.id, first.to_string());
+    assert_eq!(parent.summary, "first commit");
+    assert_eq!(git.commit(&first.to_string()[..7]).unwrap().id, parent.id);
+    assert!(git.commit("no-such-branch").is_err());
+}
+
+#[test]
+fn reflog_lists_newest_first() {
+    let fx = Fixture::new();
+    let first = fx.commit("first", 1_700_000_000);
+    let second = fx.commit("second", 1_700_000_060);
+
+    let git = fx.open();
+    let entries = git.reflog("refs/heads/main").unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].new_id, second.to_string());
+    assert_eq!(entries[0].old_id, first.to_string());
+    assert_eq!(entries[1].new_id, first.to_string());
+    assert!(entries[1].old_id.chars().all(|c| c == '0'));
+    assert!(entries[0].message.contains("second"));
+    assert!(entries[0].time >= entries[1].time);
+}
+
+#[test]
+fn live_branches_include_remotes_and_unborn_head() {
+    let fx = Fixture::new();
+    let id = fx.commit("init", 1_700_000_000);
+    fx.repo
+        .remote("origin", "https://example.invalid/repo.git")
+        .unwrap();
+    fx.repo
+        .reference(
+            "refs/remotes/origin/feature/remote-only",
+            id,
+            false,
+            "fetch",
+        )
+        .unwrap();
+    fx.repo
+        .reference_symbolic(
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/feature/remote-only",
+            false,
+            "fetch",
+        )
+        .unwrap();
+    fx.switch("next");
+
+    let git = fx.open();
+    assert_eq!(git.remote_branches().unwrap(), ["feature/remote-only"]);
+    let mut live = git.live_branches().unwrap().into_iter().collect::<Vec<_>>();
+    live.sort();
+    assert_eq!(live, ["feature/remote-only", "main", "next"]);
+}
@@ -0,0 +1,156 @@
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+mod en;
+mod ko;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    Ko,
+}
+
+// 0 = 아직 결정되지 않음 (첫 조회 시 환경 변수로 결정)
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+impl Locale {
+    /// "ko", "ko_KR.UTF-8", "en-US" 같은 값을 해석
+    pub fn parse(value: &str) -> Option<Self> {
+        let lang = value
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match lang.as_str() {
+            "ko" => Some(Locale::Ko),
+            "en" | "c" | "posix" => Some(Locale::En),
+            _ => None,
+        }
+    }
+
+    /// `ui.locale` 설정 값 해석 ("auto"는 환경 변수 기준)
+    pub fn from_setting(setting: &str) -> Option<Self> {
+        if setting.trim().eq_ignore_ascii_case("auto") {
+            return Some(Self::from_env());
+        }
+        Self::parse(setting.trim())
+    }
+
+    /// BBIRIBARABU_UI_LOCALE > LC_ALL > LC_MESSAGES > LANG, 기본값은 영어
+    pub fn from_env() -> Self {
+        ["BBIRIBARABU_UI_LOCALE", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .filter(|value| !value.is_empty() && !value.eq_ignore_ascii_case("auto"))
+            .find_map(|value| Self::parse(&value))
+            .unwrap_or(Locale::En)
+    }
+
+    fn messages(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => en::MESSAGES,
+            Locale::Ko => ko::MESSAGES,
+        }
+    }
+}
+
+pub fn set_locale(locale: Locale) {
+    let value = match locale {
+        Locale::En => 1,
+        Locale::Ko => 2,
+    };
+    CURRENT.store(value, Ordering::Relaxed);
+}
+
+pub fn locale() -> Locale {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Locale::En,
+        2 => Locale::Ko,
+        _ => {
+            let locale = Locale::from_env();
+            set_locale(locale);
+            locale
+        }
+    }
+}
+
+/// 설정 파일의 `ui.locale` 적용 (잘못된 값은 무시)
+pub fn apply_setting(setting: &str) {
+    if let Some(locale) = Locale::from_setting(setting) {
+        set_locale(locale);
+    }
+}
+
+/// 현재 언어 카탈로그에서 찾고, 없으면 영어 카탈로그로 대체
+pub fn lookup(key: &str) -> Option<&'static str> {
+    let find = |messages: &'static [(&'static str, &'static str)]| {
+        messages.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+    };
+    find(locale().messages()).or_else(|| find(Locale::En.messages()))
+}
+
+pub fn text(key: &str) -> String {
+    lookup(key).unwrap_or(key).to_string()
+}
+
+/// `{name}` 자리표시자를 값으로 치환. 템플릿을 한 번만 훑으므로 값 안의 `{...}`는 그대로 남는다.
+pub fn format(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            let (_, value) = args.iter().find(|(arg, _)| *arg == name)?;
+            Some((value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                out.push_str(&value.to_string());
+                rest = &after[end + 1..];
+            }
+            // 인자가 없는 자리표시자나 짝 없는 중괄호는 그대로 둔다
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 카탈로그 메시지 조회: `tr!("log.added")`, `tr!("cli.not_found", id = id)`
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::i18n::text($key)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format(
+            &$crate::i18n::text($key),
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+],
+        )
+    };
+}
+
+pub(crate) use tr;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_substitutes_in_one_pass() {
+        let branch = "feat/{error}";
+        let error = "boom {path}";
+        assert_eq!(
+            format(
+                "{branch}: {error} ({path}) {missing} {",
+                &[("branch", &branch), ("error", &error), ("path", &"/tmp")]
+            ),
+            "feat/{error}: boom {path} (/tmp) {missing} {"
+        );
+        assert_eq!(format("{{n}}", &[("n", &1)]), "{1}");
+    }
+}
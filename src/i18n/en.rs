@@ -0,0 +1,506 @@
+pub const MESSAGES: &[(&str, &str)] = &[
+    // CLI help
+    ("help.about", "Branch-context logbook"),
+    ("help.arg.branch", "Target branch (default: current branch)"),
+    ("help.add.about", "Add a log to the current branch"),
+    ("help.add.arg.text", "Text to record"),
+    ("help.list.about", "List logs of the current branch"),
+    ("help.edit.about", "Edit the text of a log"),
+    (
+        "help.search.about",
+        "Search logs of the current branch (ranked, typo-tolerant)",
+    ),
+    ("help.search.arg.query", "Search text"),
+    ("help.search.arg.limit", "Only print the top N"),
+    ("help.edit.arg.id", "Id of the log to edit"),
+    ("help.edit.arg.text", "New text"),
+    ("help.rm.about", "Delete a log"),
+    ("help.rm.arg.id", "Id of the log to delete"),
+    (
+        "help.replay.about",
+        "Play back the terminal recording attached to a log",
+    ),
+    ("help.replay.arg.id", "Id of the log with the recording"),
+    ("help.replay.arg.speed", "Playback speed multiplier"),
+    (
+        "help.replay.arg.idle_limit",
+        "Shorten pauses to at most N seconds",
+    ),
+    ("help.adopt.about", "Move scratch notes into a branch"),
+    (
+        "help.adopt.arg.from",
+        "Scratch logbook to move (directory path or name, default: current directory)",
+    ),
+    (
+        "help.gc.about",
+        "Clean up logbooks of deleted branches (archives by default)",
+    ),
+    ("help.gc.arg.dry_run", "Only list what would be cleaned up"),
+    ("help.gc.arg.purge", "Delete instead of archiving"),
+    (
+        "help.prompt.about",
+        "Print a shell prompt segment (open todos, time since the last note)",
+    ),
+    (
+        "help.prompt.arg.format",
+        "Output format ({branch}, {count}, {todos} and {age} are replaced)",
+    ),
+    ("help.voice.about", "Transcribe speech and add it as a log"),
+    ("help.voice.arg.seconds", "Recording length in seconds"),
+    ("help.config.about", "Show or change settings"),
+    ("help.config.get.about", "Show a setting"),
+    (
+        "help.config.get.arg.key",
+        "Setting key (e.g. voice.language)",
+    ),
+    (
+        "help.config.set.about",
+        "Save a setting (default: repo config file)",
+    ),
+    (
+        "help.config.set.arg.key",
+        "Setting key (e.g. voice.language)",
+    ),
+    ("help.config.set.arg.value", "Value to save"),
+    (
+        "help.config.set.arg.global",
+        "Save to the user config (~/.config/bbiribarabu/config.toml)",
+    ),
+    (
+        "help.config.list.about",
+        "Show all settings and where they come from",
+    ),
+    ("help.completions.about", "Print a shell completion script"),
+    ("help.completions.arg.shell", "Target shell"),
+    ("help.man.about", "Print the man page (roff)"),
+    (
+        "help.global.about",
+        "Show logs across all registered repos (register with global.enabled)",
+    ),
+    (
+        "help.global.list.about",
+        "List logs of every repo and branch (oldest first)",
+    ),
+    ("help.global.list.arg.limit", "Only print the latest N"),
+    (
+        "help.global.search.about",
+        "Search logs across every repo and branch",
+    ),
+    (
+        "help.global.search.arg.query",
+        "Search text (case-insensitive)",
+    ),
+    ("help.global.repos.about", "List registered repos"),
+    // CLI messages
+    (
+        "cli.completions_failed",
+        "Failed to write completion script: {error}",
+    ),
+    ("cli.man_failed", "Failed to write man page: {error}"),
+    ("cli.init_failed", "Initialization failed: {error}"),
+    ("cli.add_failed", "Failed to add log: {error}"),
+    ("cli.added", "✅ Log added [{time}] {text}"),
+    ("cli.list_failed", "Failed to list logs: {error}"),
+    ("cli.list_empty", "📭 No logs on the current branch"),
+    ("cli.search_empty", "🔍 No matches for: {query}"),
+    ("cli.edit_failed", "Failed to edit log: {error}"),
+    ("cli.edited", "✏️ Log edited {id}"),
+    ("cli.rm_failed", "Failed to delete log: {error}"),
+    ("cli.removed", "🗑️ Log deleted {id}"),
+    ("cli.not_found", "No log with id: {id}"),
+    ("cli.no_cast", "Log {id} has no terminal recording"),
+    ("cli.not_positive", "expected a number greater than 0"),
+    ("cli.negative", "expected a number of 0 or more"),
+    (
+        "cli.cast_hint",
+        "  ▶ terminal recording: bbiribarabu replay {id}",
+    ),
+    ("cli.replay_failed", "Failed to replay: {error}"),
+    (
+        "cli.replay_small_terminal",
+        "Recorded at {size}, this terminal is {current}; lines may wrap",
+    ),
+    ("cli.voice_empty", "No speech was recognized"),
+    ("cli.voice_added", "✅ Voice log added [{time}] {text}"),
+    ("cli.tui_failed", "TUI error: {error}"),
+    ("cli.config_read_failed", "Failed to read config: {error}"),
+    ("cli.config_save_failed", "Failed to save config: {error}"),
+    ("cli.config_saved", "✅ {key} = {value} ({path})"),
+    (
+        "cli.scratch_notice",
+        "📝 No git branch here, writing to the scratch logbook ({name})",
+    ),
+    (
+        "cli.adopt_not_repo",
+        "Run this inside a git repo to move notes into a branch",
+    ),
+    (
+        "cli.adopt_no_branch",
+        "There is no current branch. Pass the target with --branch",
+    ),
+    ("cli.adopt_empty", "No scratch notes to move: {name}"),
+    (
+        "cli.adopted",
+        "📦 Moved {count} scratch notes: {name} → {branch}",
+    ),
+    (
+        "app.cwd_failed",
+        "Cannot get the current directory: {error}",
+    ),
+    (
+        "cli.global_failed",
+        "Failed to read the global index: {error}",
+    ),
+    ("cli.global_skipped", "⚠️ Skipped {path}: {error}"),
+    (
+        "cli.global_pruned",
+        "🧹 Removed missing repo from the global index: {path}",
+    ),
+    (
+        "cli.global_disabled",
+        "The global index is off. Enable it with: bbiribarabu config set --global global.enabled true",
+    ),
+    ("cli.global_empty", "📭 No logs in registered repos"),
+    ("cli.gc_not_repo", "gc only works inside a git repo"),
+    ("cli.gc_failed", "gc failed: {error}"),
+    ("cli.gc_empty", "✨ No orphaned logbooks"),
+    ("cli.gc_would", "{branch} ({count} notes)"),
+    (
+        "cli.gc_dry_run_hint",
+        "Dry run: nothing changed. Run without --dry-run to archive, or with --purge to delete.",
+    ),
+    (
+        "cli.gc_archived",
+        "📦 Archived {branch} ({count} notes) → {path}",
+    ),
+    ("cli.gc_purged", "🗑️ Deleted {branch} ({count} notes)"),
+    (
+        "registry.read_failed",
+        "Failed to read the global index: {error} ({path})",
+    ),
+    (
+        "registry.parse_failed",
+        "Failed to parse the global index: {error} ({path})",
+    ),
+    (
+        "registry.dir_create_failed",
+        "Failed to create the data directory: {error}",
+    ),
+    (
+        "registry.serialize_failed",
+        "Failed to serialize the global index: {error}",
+    ),
+    (
+        "registry.write_failed",
+        "Failed to write the global index: {error} ({path})",
+    ),
+    // Config
+    ("config.invalid", "Invalid config value: {error}"),
+    ("config.unknown_key", "Unknown config key: {key}"),
+    ("config.invalid_key", "Invalid key for {key}: {error}"),
+    (
+        "config.invalid_locale",
+        "Invalid locale for {key}: {value} (auto, en, ko)",
+    ),
+    (
+        "config.invalid_layout",
+        "Invalid layout for {key}: {value} (side, stacked, terminal, logs)",
+    ),
+    (
+        "config.no_user_dir",
+        "Cannot find the user config directory (HOME is not set)",
+    ),
+    (
+        "config.no_data_dir",
+        "Cannot find the data directory (neither HOME nor XDG_DATA_HOME is set)",
+    ),
+    (
+        "config.type_mismatch",
+        "Wrong type for {key} (expected: {expected})",
+    ),
+    (
+        "config.invalid_value",
+        "Invalid value for {key}: {value} (expected: {expected})",
+    ),
+    (
+        "config.read_failed",
+        "Failed to read config file: {error} ({path})",
+    ),
+    (
+        "config.parse_failed",
+        "Failed to parse config file: {error} ({path})",
+    ),
+    (
+        "config.dir_create_failed",
+        "Failed to create config directory: {error}",
+    ),
+    (
+        "config.serialize_failed",
+        "Failed to serialize config: {error}",
+    ),
+    (
+        "config.write_failed",
+        "Failed to write config file: {error} ({path})",
+    ),
+    // Log store
+    (
+        "store.dir_create_failed",
+        "Failed to create log directory: {error}",
+    ),
+    (
+        "store.dir_read_failed",
+        "Failed to read log directory: {error}",
+    ),
+    (
+        "store.read_failed",
+        "Failed to read log file: {error} ({path})",
+    ),
+    ("store.parse_failed", "Failed to parse log JSON: {error}"),
+    (
+        "store.serialize_failed",
+        "Failed to serialize log JSON: {error}",
+    ),
+    (
+        "store.write_failed",
+        "Failed to write log file: {error} ({path})",
+    ),
+    // Terminal recordings
+    (
+        "cast.write_failed",
+        "Failed to write terminal recording: {error} ({path})",
+    ),
+    (
+        "cast.read_failed",
+        "Failed to read terminal recording: {error} ({path})",
+    ),
+    ("cast.empty", "The recording is empty"),
+    ("cast.parse_failed", "Bad recording, line {line}: {error}"),
+    (
+        "cast.unsupported_version",
+        "Unsupported asciicast version {version} (only v2)",
+    ),
+    (
+        "cast.play_failed",
+        "Failed to write to the terminal: {error}",
+    ),
+    // git
+    ("git.not_repo", "Not a git repo"),
+    ("git.bare_repo", "Bare repositories have no working tree"),
+    ("git.failed", "git error: {error}"),
+    ("git.rev_not_found", "Cannot find commit {rev}: {error}"),
+    ("git.no_branch", "Cannot find the current branch"),
+    // File watcher
+    (
+        "watch.start_failed",
+        "Failed to start file watcher: {error}",
+    ),
+    ("watch.path_failed", "Failed to watch {path}: {error}"),
+    // Voice
+    ("voice.failed", "Speech recognition failed: {error}"),
+    ("voice.model_failed", "Model setup failed: {error}"),
+    ("voice.sample_rate_low", "Input sample rate is too low"),
+    ("voice.cancelled", "Recording cancelled"),
+    ("voice.no_speech", "No speech detected"),
+    ("voice.no_segment", "No usable speech segment found"),
+    ("voice.no_mic", "No microphone found"),
+    (
+        "voice.device_config_failed",
+        "Failed to configure input device: {error}",
+    ),
+    ("voice.stream_failed", "Failed to create stream: {error}"),
+    ("voice.unsupported_format", "Unsupported sample format"),
+    (
+        "voice.stream_play_failed",
+        "Failed to start stream: {error}",
+    ),
+    ("voice.empty_audio", "Recorded audio is empty"),
+    (
+        "voice.whisper_load_failed",
+        "Failed to load Whisper model: {error}",
+    ),
+    (
+        "voice.whisper_state_failed",
+        "Failed to create Whisper state: {error}",
+    ),
+    ("voice.whisper_failed", "Whisper inference failed: {error}"),
+    ("voice.segment_failed", "Failed to read segments: {error}"),
+    (
+        "voice.segment_text_failed",
+        "Failed to read segment text: {error}",
+    ),
+    // Model download
+    ("model.not_file", "Model path is not a file"),
+    ("model.downloading", "Model not found, downloading..."),
+    ("model.downloaded", "Model downloaded"),
+    (
+        "model.cwd_failed",
+        "Cannot get the current directory: {error}",
+    ),
+    (
+        "model.dir_create_failed",
+        "Failed to create model directory: {error}",
+    ),
+    ("model.request_failed", "Download request failed: {error}"),
+    ("model.response_error", "Download response error: {error}"),
+    (
+        "model.tmp_create_failed",
+        "Failed to create temp file: {error}",
+    ),
+    (
+        "model.save_download_failed",
+        "Failed to save download: {error}",
+    ),
+    ("model.flush_failed", "Failed to flush download: {error}"),
+    ("model.save_failed", "Failed to save model file: {error}"),
+    ("model.path_utf8", "Model path is not valid UTF-8"),
+    // TUI
+    ("tui.header.repo", " repo: "),
+    ("tui.header.branch", "branch: "),
+    ("tui.header.dir", " dir: "),
+    ("tui.header.scratch", "scratch logbook"),
+    (
+        "tui.header.orphans",
+        " | orphaned logbooks: {count} (bbiribarabu gc)",
+    ),
+    ("tui.title.terminal_focus", " Terminal (focus) "),
+    (
+        "tui.title.terminal_leader",
+        " Terminal (focus, {leader} then a key for shortcuts) ",
+    ),
+    ("tui.title.leader_pending", "{leader} … "),
+    ("tui.title.terminal", " Terminal "),
+    ("tui.title.logs", " Logs "),
+    ("tui.title.editing", " Enter log (Enter=save, Esc=cancel) "),
+    (
+        "tui.title.normal",
+        " Log input ({add}=add, {edit}=edit, {delete}=del, {search}=search, {voice}=voice, {toggle}=switch, {quit}=quit) ",
+    ),
+    (
+        "tui.title.terminal_select",
+        " Select (arrows=move, Space=mark, Enter=save, Esc=cancel) ",
+    ),
+    (
+        "tui.title.terminal_search",
+        " Search scrollback: {query}  {index}/{count} (Up/Down=prev/next, Enter=stay, Esc=back) ",
+    ),
+    (
+        "tui.title.terminal_search_none",
+        " Search scrollback: {query}  no matches (Esc=back) ",
+    ),
+    (
+        "tui.title.terminal_search_empty",
+        " Search scrollback: type to search (Esc=back) ",
+    ),
+    (
+        "tui.title.snippet",
+        " Caption for the {lines}-line snippet (Enter=save, Esc=cancel) ",
+    ),
+    ("tui.snippet_marker", "[+{lines} lines]"),
+    ("tui.snippet_caption", "Terminal output"),
+    ("tui.snippet_failed", "Failed to save snippet: {error}"),
+    (
+        "tui.title.cast",
+        " Caption for the terminal recording (Enter=save, Esc=discard) ",
+    ),
+    ("tui.title.recording", "● REC "),
+    ("tui.cast_marker", "[▶ recording]"),
+    ("tui.cast_caption", "Terminal recording"),
+    ("tui.cast_started", "Recording the terminal — {key} stops"),
+    ("tui.cast_discarded", "Recording discarded"),
+    ("tui.cast_failed", "Terminal recording failed: {error}"),
+    ("tui.layout.side", "side by side"),
+    ("tui.layout.stacked", "stacked"),
+    ("tui.layout.terminal", "terminal only"),
+    ("tui.layout.logs", "logs only"),
+    ("tui.layout_changed", "Layout: {layout}"),
+    ("tui.layout_split", "Terminal panel: {percent}%"),
+    (
+        "tui.layout_save_failed",
+        "Failed to save the layout: {error}",
+    ),
+    ("tui.selection_empty", "Nothing selected"),
+    ("tui.title.confirm", " Confirm delete (y/n) "),
+    ("tui.title.search", " Search (Enter=apply, Esc=clear) "),
+    ("tui.title.unfocused", " Log input ({toggle} to focus) "),
+    (
+        "tui.confirm_delete",
+        "Delete this log? [y] delete / [n] cancel",
+    ),
+    ("tui.recording", "Recording... press {key} to stop"),
+    ("tui.recording_cancelled", "Recording cancelled"),
+    ("tui.saving", "Saving log..."),
+    ("tui.saved", "Log saved"),
+    ("tui.voice_empty", "Nothing was recognized"),
+    ("tui.voice_log_failed", "Failed to save voice log: {error}"),
+    ("tui.voice_failed", "Speech recognition failed"),
+    ("tui.log_deleted", "log deleted"),
+    ("tui.log_updated", "log updated"),
+    ("tui.branch_switched", "Switched to {branch}"),
+    ("tui.recap.title", " Back on {branch} "),
+    ("tui.recap.last_note", "Last note: {elapsed} ago"),
+    ("tui.recap.no_notes", "No notes on this branch yet"),
+    ("tui.recap.recent", "Recent notes"),
+    ("tui.recap.todos", "Open todos ({count})"),
+    ("tui.recap.more_todos", "… {count} older"),
+    ("tui.recap.dismiss", "Press any key to close"),
+    ("tui.elapsed.days", "{days}d {hours}h"),
+    ("tui.elapsed.hours", "{hours}h {minutes}m"),
+    ("tui.elapsed.minutes", "{minutes}m"),
+    ("tui.elapsed.now", "under a minute"),
+    (
+        "tui.command_failed",
+        "✗ `{command}` failed (exit {code}) — {key} saves it as a note",
+    ),
+    (
+        "tui.command_failed_in_tab",
+        "✗ `{command}` failed (exit {code}) in tab {tab}",
+    ),
+    ("tui.command_saved", "Command saved as a note"),
+    (
+        "tui.tab_open_failed",
+        "Could not open a terminal tab: {error}",
+    ),
+    ("tui.tab_last", "The last terminal tab stays open"),
+    ("tui.tab_exited", "The shell in tab {tab} exited"),
+    ("tui.shell_exited", "Shell exited (exit {code})"),
+    ("tui.shell_restart_root", "{key}  restart in the repo root"),
+    ("tui.shell_restart_cwd", "{key}  restart in {dir}"),
+    ("tui.shell_close_tab", "{key}  close this tab"),
+    ("tui.shell_quit", "{key}  quit"),
+    (
+        "tui.shell_restart_failed",
+        "Could not restart the shell: {error}",
+    ),
+    ("tui.command_log_failed", "Failed to save command: {error}"),
+    ("tui.no_command", "No finished command yet"),
+    (
+        "tui.shell_integration_off",
+        "Shell integration is off (set shell.integration = true; bash/zsh only)",
+    ),
+    // PTY
+    ("pty.open_failed", "openpty failed: {error}"),
+    ("pty.spawn_failed", "spawn shell failed: {error}"),
+    ("pty.reader_failed", "clone reader failed: {error}"),
+    ("pty.writer_failed", "take_writer failed: {error}"),
+    (
+        "shell.unsupported",
+        "shell integration does not support `{shell}`",
+    ),
+    (
+        "shell.script_failed",
+        "failed to write shell integration script: {error}",
+    ),
+    (
+        "shell.bad_quote",
+        "shell.command / shell.env: unterminated quote in `{line}`",
+    ),
+    ("shell.bad_env", "shell.env: `{entry}` is not NAME=VALUE"),
+    // Key bindings
+    ("keys.empty", "empty key binding"),
+    (
+        "keys.unknown_modifier",
+        "unknown modifier `{modifier}` in `{spec}`",
+    ),
+    ("keys.unknown_key", "unknown key `{key}` in `{spec}`"),
+    ("keys.invalid", "Invalid key binding keys.{name}: {error}"),
+];
@@ -0,0 +1,461 @@
+pub const MESSAGES: &[(&str, &str)] = &[
+    // CLI 도움말
+    ("help.about", "브랜치 컨텍스트 로그 도구"),
+    ("help.arg.branch", "대상 브랜치 (기본값: 현재 브랜치)"),
+    ("help.add.about", "현재 브랜치에 로그 추가"),
+    ("help.add.arg.text", "기록할 텍스트"),
+    ("help.list.about", "현재 브랜치 로그 목록 조회"),
+    ("help.edit.about", "로그 텍스트 수정"),
+    (
+        "help.search.about",
+        "현재 브랜치 로그 검색 (관련도순, 오타 허용)",
+    ),
+    ("help.search.arg.query", "검색어"),
+    ("help.search.arg.limit", "상위 N개만 출력"),
+    ("help.edit.arg.id", "수정할 로그 id"),
+    ("help.edit.arg.text", "새 텍스트"),
+    ("help.rm.about", "로그 삭제"),
+    ("help.rm.arg.id", "삭제할 로그 id"),
+    ("help.replay.about", "로그에 붙은 터미널 녹화 재생"),
+    ("help.replay.arg.id", "녹화가 붙은 로그 id"),
+    ("help.replay.arg.speed", "재생 속도 배율"),
+    (
+        "help.replay.arg.idle_limit",
+        "멈춰 있던 구간을 최대 N초로 줄임",
+    ),
+    ("help.adopt.about", "임시 로그북의 노트를 브랜치로 옮기기"),
+    (
+        "help.adopt.arg.from",
+        "옮길 임시 로그북 (디렉터리 경로 또는 이름, 기본값: 현재 디렉터리)",
+    ),
+    (
+        "help.gc.about",
+        "삭제된 브랜치의 로그북 정리 (기본: 압축 보관)",
+    ),
+    (
+        "help.gc.arg.dry_run",
+        "대상만 출력하고 아무것도 바꾸지 않음",
+    ),
+    ("help.gc.arg.purge", "보관하지 않고 완전히 삭제"),
+    (
+        "help.prompt.about",
+        "쉘 프롬프트용 요약 출력 (할 일 수, 마지막 로그 이후 시간)",
+    ),
+    (
+        "help.prompt.arg.format",
+        "출력 형식 ({branch}, {count}, {todos}, {age} 치환)",
+    ),
+    ("help.voice.about", "음성 인식 후 로그 추가"),
+    ("help.voice.arg.seconds", "녹음 시간(초)"),
+    ("help.config.about", "설정 조회/변경"),
+    ("help.config.get.about", "설정 값 조회"),
+    ("help.config.get.arg.key", "설정 키 (예: voice.language)"),
+    (
+        "help.config.set.about",
+        "설정 값 저장 (기본: 레포 설정 파일)",
+    ),
+    ("help.config.set.arg.key", "설정 키 (예: voice.language)"),
+    ("help.config.set.arg.value", "저장할 값"),
+    (
+        "help.config.set.arg.global",
+        "사용자 설정(~/.config/bbiribarabu/config.toml)에 저장",
+    ),
+    ("help.config.list.about", "전체 설정과 출처 조회"),
+    ("help.completions.about", "쉘 자동완성 스크립트 출력"),
+    ("help.completions.arg.shell", "대상 쉘"),
+    ("help.man.about", "man 페이지 출력 (roff)"),
+    (
+        "help.global.about",
+        "등록된 모든 repo의 로그 조회 (global.enabled로 등록)",
+    ),
+    (
+        "help.global.list.about",
+        "모든 repo/브랜치의 로그 목록 (시간순)",
+    ),
+    ("help.global.list.arg.limit", "최근 N개만 출력"),
+    ("help.global.search.about", "모든 repo/브랜치에서 로그 검색"),
+    ("help.global.search.arg.query", "검색어 (대소문자 무시)"),
+    ("help.global.repos.about", "등록된 repo 목록"),
+    // CLI 메시지
+    (
+        "cli.completions_failed",
+        "자동완성 스크립트 출력 실패: {error}",
+    ),
+    ("cli.man_failed", "man 페이지 출력 실패: {error}"),
+    ("cli.init_failed", "초기화 실패: {error}"),
+    ("cli.add_failed", "로그 추가 실패: {error}"),
+    ("cli.added", "✅ 로그 추가됨 [{time}] {text}"),
+    ("cli.list_failed", "로그 조회 실패: {error}"),
+    ("cli.list_empty", "📭 현재 브랜치에 로그가 없습니다"),
+    ("cli.search_empty", "🔍 검색 결과가 없습니다: {query}"),
+    ("cli.edit_failed", "로그 수정 실패: {error}"),
+    ("cli.edited", "✏️ 로그 수정됨 {id}"),
+    ("cli.rm_failed", "로그 삭제 실패: {error}"),
+    ("cli.removed", "🗑️ 로그 삭제됨 {id}"),
+    ("cli.not_found", "해당 id의 로그가 없습니다: {id}"),
+    ("cli.no_cast", "{id} 로그에는 터미널 녹화가 없습니다"),
+    ("cli.not_positive", "0보다 큰 수여야 합니다"),
+    ("cli.negative", "0 이상의 수여야 합니다"),
+    ("cli.cast_hint", "  ▶ 터미널 녹화: bbiribarabu replay {id}"),
+    ("cli.replay_failed", "재생 실패: {error}"),
+    (
+        "cli.replay_small_terminal",
+        "{size} 크기로 녹화했는데 지금 터미널은 {current}라서 줄이 어긋날 수 있습니다",
+    ),
+    ("cli.voice_empty", "인식된 텍스트가 없습니다"),
+    ("cli.voice_added", "✅ 보이스 로그 추가됨 [{time}] {text}"),
+    ("cli.tui_failed", "TUI 실행 오류: {error}"),
+    ("cli.config_read_failed", "설정 읽기 실패: {error}"),
+    ("cli.config_save_failed", "설정 저장 실패: {error}"),
+    ("cli.config_saved", "✅ {key} = {value} 저장됨 ({path})"),
+    (
+        "cli.scratch_notice",
+        "📝 git 브랜치가 없어 임시 로그북에 기록합니다 ({name})",
+    ),
+    (
+        "cli.adopt_not_repo",
+        "git repo 안에서 실행해야 노트를 옮길 수 있습니다",
+    ),
+    (
+        "cli.adopt_no_branch",
+        "현재 브랜치가 없습니다. --branch로 대상 브랜치를 지정하세요",
+    ),
+    ("cli.adopt_empty", "옮길 임시 로그가 없습니다: {name}"),
+    (
+        "cli.adopted",
+        "📦 임시 로그 {count}개를 옮겼습니다: {name} → {branch}",
+    ),
+    (
+        "app.cwd_failed",
+        "현재 디렉터리를 가져올 수 없습니다: {error}",
+    ),
+    ("cli.global_failed", "전역 목록 조회 실패: {error}"),
+    ("cli.global_skipped", "⚠️ {path} 건너뜀: {error}"),
+    (
+        "cli.global_pruned",
+        "🧹 사라진 repo를 전역 목록에서 제거: {path}",
+    ),
+    (
+        "cli.global_disabled",
+        "전역 목록이 꺼져 있습니다. 켜려면: bbiribarabu config set --global global.enabled true",
+    ),
+    ("cli.global_empty", "📭 등록된 repo에 로그가 없습니다"),
+    ("cli.gc_not_repo", "gc는 git repo 안에서만 쓸 수 있습니다"),
+    ("cli.gc_failed", "gc 실패: {error}"),
+    ("cli.gc_empty", "✨ 정리할 로그북이 없습니다"),
+    ("cli.gc_would", "{branch} (로그 {count}개)"),
+    (
+        "cli.gc_dry_run_hint",
+        "미리보기라 아무것도 바꾸지 않았습니다. --dry-run 없이 실행하면 보관, --purge를 붙이면 삭제합니다.",
+    ),
+    (
+        "cli.gc_archived",
+        "📦 {branch} 보관 (로그 {count}개) → {path}",
+    ),
+    ("cli.gc_purged", "🗑️ {branch} 삭제 (로그 {count}개)"),
+    (
+        "registry.read_failed",
+        "전역 목록 읽기 실패: {error} ({path})",
+    ),
+    (
+        "registry.parse_failed",
+        "전역 목록 파싱 실패: {error} ({path})",
+    ),
+    (
+        "registry.dir_create_failed",
+        "데이터 디렉터리 생성 실패: {error}",
+    ),
+    (
+        "registry.serialize_failed",
+        "전역 목록 직렬화 실패: {error}",
+    ),
+    (
+        "registry.write_failed",
+        "전역 목록 쓰기 실패: {error} ({path})",
+    ),
+    // 설정
+    ("config.invalid", "설정 값이 올바르지 않습니다: {error}"),
+    ("config.unknown_key", "알 수 없는 설정 키: {key}"),
+    ("config.invalid_key", "설정 {key}에 잘못된 키: {error}"),
+    (
+        "config.invalid_locale",
+        "설정 {key}에 잘못된 언어: {value} (auto, en, ko)",
+    ),
+    (
+        "config.invalid_layout",
+        "설정 {key}에 잘못된 배치: {value} (side, stacked, terminal, logs)",
+    ),
+    (
+        "config.no_user_dir",
+        "사용자 설정 디렉터리를 찾을 수 없습니다 (HOME 미설정)",
+    ),
+    (
+        "config.no_data_dir",
+        "데이터 디렉터리를 찾을 수 없습니다 (HOME 또는 XDG_DATA_HOME 미설정)",
+    ),
+    (
+        "config.type_mismatch",
+        "설정 {key}의 타입이 올바르지 않습니다 (기대: {expected})",
+    ),
+    (
+        "config.invalid_value",
+        "설정 {key}에 잘못된 값: {value} (기대: {expected})",
+    ),
+    (
+        "config.read_failed",
+        "설정 파일 읽기 실패: {error} ({path})",
+    ),
+    (
+        "config.parse_failed",
+        "설정 파일 파싱 실패: {error} ({path})",
+    ),
+    (
+        "config.dir_create_failed",
+        "설정 디렉터리 생성 실패: {error}",
+    ),
+    ("config.serialize_failed", "설정 직렬화 실패: {error}"),
+    (
+        "config.write_failed",
+        "설정 파일 쓰기 실패: {error} ({path})",
+    ),
+    // 로그 저장소
+    (
+        "store.dir_create_failed",
+        "로그 디렉터리 생성 실패: {error}",
+    ),
+    ("store.dir_read_failed", "로그 디렉터리 읽기 실패: {error}"),
+    ("store.read_failed", "로그 파일 읽기 실패: {error} ({path})"),
+    ("store.parse_failed", "로그 JSON 파싱 실패: {error}"),
+    ("store.serialize_failed", "로그 JSON 직렬화 실패: {error}"),
+    (
+        "store.write_failed",
+        "로그 파일 쓰기 실패: {error} ({path})",
+    ),
+    // 터미널 녹화
+    (
+        "cast.write_failed",
+        "터미널 녹화 파일 쓰기 실패: {error} ({path})",
+    ),
+    (
+        "cast.read_failed",
+        "터미널 녹화 파일 읽기 실패: {error} ({path})",
+    ),
+    ("cast.empty", "녹화 파일이 비어 있습니다"),
+    (
+        "cast.parse_failed",
+        "녹화 파일 {line}번째 줄이 잘못됐습니다: {error}",
+    ),
+    (
+        "cast.unsupported_version",
+        "지원하지 않는 asciicast 버전입니다: {version} (v2만 지원)",
+    ),
+    ("cast.play_failed", "터미널 출력 실패: {error}"),
+    // git
+    ("git.not_repo", "git repo가 아님"),
+    ("git.bare_repo", "bare repo에는 작업 트리가 없음"),
+    ("git.failed", "git 오류: {error}"),
+    (
+        "git.rev_not_found",
+        "커밋 {rev}을(를) 찾을 수 없음: {error}",
+    ),
+    ("git.no_branch", "현재 브랜치를 찾을 수 없음"),
+    // 파일 감시
+    ("watch.start_failed", "파일 감시 시작 실패: {error}"),
+    ("watch.path_failed", "{path} 감시 실패: {error}"),
+    // 음성 인식
+    ("voice.failed", "보이스 인식 실패: {error}"),
+    ("voice.model_failed", "모델 준비 실패: {error}"),
+    ("voice.sample_rate_low", "입력 샘플레이트가 너무 낮습니다"),
+    ("voice.cancelled", "녹음이 취소되었습니다"),
+    ("voice.no_speech", "음성이 감지되지 않았습니다"),
+    ("voice.no_segment", "유효한 음성 구간을 찾지 못했습니다"),
+    ("voice.no_mic", "마이크 장치가 없습니다"),
+    (
+        "voice.device_config_failed",
+        "입력 디바이스 설정 실패: {error}",
+    ),
+    ("voice.stream_failed", "스트림 생성 실패: {error}"),
+    ("voice.unsupported_format", "지원하지 않는 샘플 포맷"),
+    ("voice.stream_play_failed", "스트림 재생 실패: {error}"),
+    ("voice.empty_audio", "녹음된 오디오가 비어 있음"),
+    (
+        "voice.whisper_load_failed",
+        "Whisper 모델 로드 실패: {error}",
+    ),
+    (
+        "voice.whisper_state_failed",
+        "Whisper state 생성 실패: {error}",
+    ),
+    ("voice.whisper_failed", "Whisper 추론 실패: {error}"),
+    ("voice.segment_failed", "세그먼트 읽기 실패: {error}"),
+    (
+        "voice.segment_text_failed",
+        "세그먼트 텍스트 읽기 실패: {error}",
+    ),
+    // 모델 다운로드
+    ("model.not_file", "모델 경로가 파일이 아닙니다"),
+    ("model.downloading", "모델이 없어 다운로드합니다..."),
+    ("model.downloaded", "모델 다운로드 완료"),
+    (
+        "model.cwd_failed",
+        "현재 디렉토리를 가져올 수 없습니다: {error}",
+    ),
+    (
+        "model.dir_create_failed",
+        "모델 디렉토리 생성 실패: {error}",
+    ),
+    ("model.request_failed", "다운로드 요청 실패: {error}"),
+    ("model.response_error", "다운로드 응답 오류: {error}"),
+    ("model.tmp_create_failed", "임시 파일 생성 실패: {error}"),
+    ("model.save_download_failed", "다운로드 저장 실패: {error}"),
+    ("model.flush_failed", "다운로드 파일 플러시 실패: {error}"),
+    ("model.save_failed", "모델 파일 저장 실패: {error}"),
+    ("model.path_utf8", "모델 경로가 UTF-8이 아닙니다"),
+    // TUI
+    ("tui.header.repo", " 레포: "),
+    ("tui.header.branch", "브랜치: "),
+    ("tui.header.dir", " 디렉터리: "),
+    ("tui.header.scratch", "임시 로그북"),
+    (
+        "tui.header.orphans",
+        " | 정리할 로그북: {count}개 (bbiribarabu gc)",
+    ),
+    ("tui.title.terminal_focus", " 터미널 (포커스) "),
+    (
+        "tui.title.terminal_leader",
+        " 터미널 (포커스, 단축키는 {leader} 다음에) ",
+    ),
+    ("tui.title.leader_pending", "{leader} … "),
+    ("tui.title.terminal", " 터미널 "),
+    ("tui.title.logs", " 로그 "),
+    ("tui.title.editing", " 로그 입력 (Enter=저장, Esc=취소) "),
+    (
+        "tui.title.normal",
+        " 로그 입력 ({add}=추가, {edit}=수정, {delete}=삭제, {search}=검색, {voice}=음성, {toggle}=전환, {quit}=종료) ",
+    ),
+    (
+        "tui.title.terminal_select",
+        " 선택 (방향키=이동, Space=시작점, Enter=저장, Esc=취소) ",
+    ),
+    (
+        "tui.title.terminal_search",
+        " 스크롤백 검색: {query}  {index}/{count} (Up/Down=이전/다음, Enter=여기서 멈춤, Esc=돌아가기) ",
+    ),
+    (
+        "tui.title.terminal_search_none",
+        " 스크롤백 검색: {query}  결과 없음 (Esc=돌아가기) ",
+    ),
+    (
+        "tui.title.terminal_search_empty",
+        " 스크롤백 검색: 찾을 내용을 입력하세요 (Esc=돌아가기) ",
+    ),
+    (
+        "tui.title.snippet",
+        " {lines}줄 출력에 붙일 설명 (Enter=저장, Esc=취소) ",
+    ),
+    ("tui.snippet_marker", "[+{lines}줄]"),
+    ("tui.snippet_caption", "터미널 출력"),
+    ("tui.snippet_failed", "출력 저장 실패: {error}"),
+    (
+        "tui.title.cast",
+        " 터미널 녹화에 붙일 설명 (Enter=저장, Esc=버리기) ",
+    ),
+    ("tui.title.recording", "● 녹화 중 "),
+    ("tui.cast_marker", "[▶ 녹화]"),
+    ("tui.cast_caption", "터미널 녹화"),
+    ("tui.cast_started", "터미널 녹화 중 — {key}로 끝내기"),
+    ("tui.cast_discarded", "녹화를 버렸습니다"),
+    ("tui.cast_failed", "터미널 녹화 실패: {error}"),
+    ("tui.layout.side", "좌우로 나란히"),
+    ("tui.layout.stacked", "위아래로"),
+    ("tui.layout.terminal", "터미널만"),
+    ("tui.layout.logs", "로그만"),
+    ("tui.layout_changed", "배치: {layout}"),
+    ("tui.layout_split", "터미널 패널: {percent}%"),
+    ("tui.layout_save_failed", "배치 저장 실패: {error}"),
+    ("tui.selection_empty", "선택한 내용이 없습니다"),
+    ("tui.title.confirm", " 삭제 확인 (y/n) "),
+    ("tui.title.search", " 검색 (Enter=적용, Esc=해제) "),
+    ("tui.title.unfocused", " 로그 입력 ({toggle}로 포커스) "),
+    (
+        "tui.confirm_delete",
+        "정말 이 로그를 삭제할까요? [y] 삭제 / [n] 취소",
+    ),
+    ("tui.recording", "녹음중... {key} 누르면 종료"),
+    ("tui.recording_cancelled", "녹음 취소됨"),
+    ("tui.saving", "로그 저장중입니다"),
+    ("tui.saved", "로그 저장되었습니다"),
+    ("tui.voice_empty", "보이스 인식 결과 없음"),
+    ("tui.voice_log_failed", "보이스 로그 실패: {error}"),
+    ("tui.voice_failed", "보이스 인식 실패"),
+    ("tui.log_deleted", "로그 삭제됨"),
+    ("tui.log_updated", "로그 수정됨"),
+    ("tui.branch_switched", "{branch} 브랜치로 전환됨"),
+    ("tui.recap.title", " {branch} 브랜치로 돌아옴 "),
+    ("tui.recap.last_note", "마지막 로그: {elapsed} 전"),
+    ("tui.recap.no_notes", "이 브랜치에는 아직 로그가 없습니다"),
+    ("tui.recap.recent", "최근 로그"),
+    ("tui.recap.todos", "남은 할 일 ({count})"),
+    ("tui.recap.more_todos", "… 이전 {count}개"),
+    ("tui.recap.dismiss", "아무 키나 누르면 닫힙니다"),
+    ("tui.elapsed.days", "{days}일 {hours}시간"),
+    ("tui.elapsed.hours", "{hours}시간 {minutes}분"),
+    ("tui.elapsed.minutes", "{minutes}분"),
+    ("tui.elapsed.now", "1분 미만"),
+    (
+        "tui.command_failed",
+        "✗ `{command}` 실패 (exit {code}) — {key}로 로그에 남기기",
+    ),
+    (
+        "tui.command_failed_in_tab",
+        "✗ {tab}번 탭에서 `{command}` 실패 (exit {code})",
+    ),
+    ("tui.command_saved", "명령을 로그로 저장했습니다"),
+    (
+        "tui.tab_open_failed",
+        "터미널 탭을 열지 못했습니다: {error}",
+    ),
+    ("tui.tab_last", "마지막 터미널 탭은 닫을 수 없습니다"),
+    ("tui.tab_exited", "{tab}번 탭의 쉘이 종료되었습니다"),
+    ("tui.shell_exited", "쉘이 종료되었습니다 (exit {code})"),
+    ("tui.shell_restart_root", "{key}  저장소 루트에서 다시 시작"),
+    ("tui.shell_restart_cwd", "{key}  {dir}에서 다시 시작"),
+    ("tui.shell_close_tab", "{key}  이 탭 닫기"),
+    ("tui.shell_quit", "{key}  종료"),
+    (
+        "tui.shell_restart_failed",
+        "쉘을 다시 시작하지 못했습니다: {error}",
+    ),
+    ("tui.command_log_failed", "명령 로그 저장 실패: {error}"),
+    ("tui.no_command", "아직 끝난 명령이 없습니다"),
+    (
+        "tui.shell_integration_off",
+        "쉘 연동이 꺼져 있습니다 (shell.integration = true, bash/zsh만 지원)",
+    ),
+    // PTY
+    ("pty.open_failed", "openpty 실패: {error}"),
+    ("pty.spawn_failed", "쉘 실행 실패: {error}"),
+    ("pty.reader_failed", "PTY reader 복제 실패: {error}"),
+    ("pty.writer_failed", "PTY writer 획득 실패: {error}"),
+    (
+        "shell.unsupported",
+        "`{shell}`은 쉘 연동을 지원하지 않습니다",
+    ),
+    ("shell.script_failed", "쉘 연동 스크립트 작성 실패: {error}"),
+    (
+        "shell.bad_quote",
+        "shell.command / shell.env: `{line}`에 닫히지 않은 따옴표가 있습니다",
+    ),
+    (
+        "shell.bad_env",
+        "shell.env: `{entry}`는 NAME=VALUE 형식이 아닙니다",
+    ),
+    // 키 바인딩
+    ("keys.empty", "빈 키 바인딩"),
+    (
+        "keys.unknown_modifier",
+        "`{spec}`에 알 수 없는 수식키 `{modifier}`",
+    ),
+    ("keys.unknown_key", "`{spec}`에 알 수 없는 키 `{key}`"),
+    ("keys.invalid", "잘못된 키 바인딩 keys.{name}: {error}"),
+];
@@ -0,0 +1,59 @@
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Local};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+
+use crate::i18n::tr;
+use crate::log::model::ArchivedLogFile;
+use crate::log::store::{LogStore, branch_file_stem};
+
+/// 로그 디렉터리 아래 보관 폴더 이름
+pub const ARCHIVE_DIRNAME: &str = "archive";
+
+/// 브랜치가 `live`에 없는 로그북 (이름순)
+pub fn orphaned(store: &LogStore, live: &HashSet<String>) -> Result<Vec<String>, String> {
+    Ok(store
+        .branches()?
+        .into_iter()
+        .filter(|branch| !live.contains(branch))
+        .collect())
+}
+
+/// 로그북을 `archive/<브랜치>.<보관 시각>.json.gz`로 압축해 옮긴다.
+/// 압축 파일을 다 쓴 뒤에 원본을 지우므로 중간에 실패해도 노트가 사라지지 않는다.
+pub fn archive(
+    store: &LogStore,
+    branch: &str,
+    deleted_at: DateTime<Local>,
+) -> Result<PathBuf, String> {
+    let dir = store.dir().join(ARCHIVE_DIRNAME);
+    fs::create_dir_all(&dir).map_err(|e| tr!("store.dir_create_failed", error = e))?;
+    let path = dir.join(format!(
+        "{}.{}.json.gz",
+        branch_file_stem(branch),
+        deleted_at.format("%Y%m%d-%H%M%S")
+    ));
+
+    let file = ArchivedLogFile {
+        branch: branch.to_string(),
+        deleted_at,
+        items: store.list(branch)?,
+    };
+    let json =
+        serde_json::to_vec_pretty(&file).map_err(|e| tr!("store.serialize_failed", error = e))?;
+    let out = File::create(&path)
+        .map_err(|e| tr!("store.write_failed", error = e, path = path.display()))?;
+    let mut encoder = GzEncoder::new(out, Compression::default());
+    encoder
+        .write_all(&json)
+        .and_then(|_| encoder.finish())
+        .and_then(|file| file.sync_all())
+        .map_err(|e| tr!("store.write_failed", error = e, path = path.display()))?;
+
+    store.remove_branch(branch)?;
+    Ok(path)
+}
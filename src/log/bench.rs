@@ -0,0 +1,149 @@
+//! 5만 개 로그에서 TUI 한 프레임과 쉘 프롬프트 한 번에 드는 저장소 비용 측정.
+//! `cargo test --release -- --ignored --nocapture bench_` 로 실행한다.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{Duration as ChronoDuration, Local};
+
+use crate::app::Workspace;
+use crate::log::model::{BranchLogFile, LogItem};
+use crate::log::store::{LogStore, branch_file_stem};
+
+const NOTES: usize = 50_000;
+const BRANCH: &str = "bench/main";
+// TUI 이벤트 루프 주기 (50ms)
+const FRAME_BUDGET: Duration = Duration::from_millis(50);
+// 쉘 프롬프트를 그릴 때마다 실행되는 `prompt` 명령 목표
+const PROMPT_BUDGET: Duration = Duration::from_millis(10);
+
+fn seed(dir: &std::path::Path) -> LogStore {
+    let words = [
+        "fix",
+        "flaky",
+        "test",
+        "deploy",
+        "staging",
+        "review",
+        "테스트",
+        "배포",
+        "리뷰",
+        "회의",
+        "정리",
+        "버그",
+    ];
+    let start = Local::now() - ChronoDuration::days(365);
+    let items = (0..NOTES)
+        .map(|i| LogItem {
+            id: i.to_string(),
+            created_at: start + ChronoDuration::seconds(i as i64 * 60),
+            text: format!(
+                "{} {} {} note {}",
+                words[i % words.len()],
+                words[(i / 7) % words.len()],
+                words[(i / 13) % words.len()],
+                i
+            ),
+            snippet: None,
+            cast: None,
+        })
+        .collect::<Vec<_>>();
+    let file = BranchLogFile {
+        branch: BRANCH.to_string(),
+        items,
+    };
+    let path = dir.join(format!("{}.json", branch_file_stem(BRANCH)));
+    std::fs::write(path, serde_json::to_string_pretty(&file).unwrap()).unwrap();
+    LogStore::new(dir).unwrap()
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let started = Instant::now();
+    let out = f();
+    (out, started.elapsed())
+}
+
+#[test]
+#[ignore]
+fn bench_frame_with_50k_notes() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = seed(dir.path());
+
+    let (first, cold) = time(|| store.snapshot(BRANCH).unwrap());
+    assert_eq!(first.len(), NOTES);
+
+    // 변경이 없을 때 매 프레임 호출되는 경로
+    let frames = 1_000;
+    let (_, warm) = time(|| {
+        for _ in 0..frames {
+            let snap = store.snapshot(BRANCH).unwrap();
+            assert!(Arc::ptr_eq(&snap, &first));
+        }
+    });
+    let per_frame = warm / frames;
+
+    // TUI 자신의 기록은 다시 읽지 않고 캐시에 반영된다
+    let (_, write) = time(|| store.append_text(BRANCH, "benchmark write").unwrap());
+    let (after_write, reread) = time(|| store.snapshot(BRANCH).unwrap());
+    assert_eq!(after_write.len(), NOTES + 1);
+
+    let (_, index_build) = time(|| store.search(BRANCH, "flaky").unwrap());
+    let (hits, search) = time(|| store.search(BRANCH, "테스ㅌ 배포").unwrap());
+    assert!(!hits.is_empty());
+
+    // 다른 프로세스가 기록하면 다음 프레임에서 다시 읽는다
+    let other = LogStore::new(dir.path()).unwrap();
+    other.append_text(BRANCH, "external write").unwrap();
+    let (external, reload) = time(|| store.snapshot(BRANCH).unwrap());
+    assert_eq!(external.len(), NOTES + 2);
+
+    eprintln!("notes:              {NOTES}");
+    eprintln!("cold load:          {cold:?}");
+    eprintln!("cached frame:       {per_frame:?}");
+    eprintln!("own write:          {write:?} (+ {reread:?} to read back)");
+    eprintln!("index build:        {index_build:?}");
+    eprintln!("indexed search:     {search:?}");
+    eprintln!("external reload:    {reload:?}");
+
+    assert!(
+        per_frame < Duration::from_millis(1),
+        "cached frame {per_frame:?}"
+    );
+    assert!(
+        reread < Duration::from_millis(1),
+        "read after own write {reread:?}"
+    );
+    assert!(search < FRAME_BUDGET, "search {search:?}");
+}
+
+#[test]
+#[ignore]
+fn bench_prompt_with_50k_notes() {
+    let dir = tempfile::tempdir().unwrap();
+    git2::Repository::init(dir.path()).unwrap();
+    let workspace = Workspace::discover_from(dir.path());
+    let log_dir = workspace.config.as_ref().unwrap().log_dir(&workspace.root);
+    std::fs::create_dir_all(&log_dir).unwrap();
+    seed(&log_dir);
+
+    // 요약 파일이 없으면 처음 한 번은 로그북 전체를 읽는다
+    let (first, cold) = time(|| crate::prompt::segment(&workspace, Some(BRANCH.into()), None));
+    assert!(first.is_some());
+
+    // main이 하는 일 그대로: repo 탐색, 설정 읽기, 요약 읽기
+    let runs = 100;
+    let (_, warm) = time(|| {
+        for _ in 0..runs {
+            let workspace = Workspace::discover_from(dir.path());
+            let segment = crate::prompt::segment(&workspace, Some(BRANCH.into()), None);
+            assert_eq!(segment, first);
+        }
+    });
+    let per_prompt = warm / runs;
+
+    eprintln!("notes:              {NOTES}");
+    eprintln!("first prompt:       {cold:?}");
+    eprintln!("prompt:             {per_prompt:?}");
+
+    assert!(per_prompt < PROMPT_BUDGET, "prompt {per_prompt:?}");
+}
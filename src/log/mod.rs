@@ -1,2 +1,11 @@
+pub mod archive;
 pub mod model;
+pub mod recap;
+pub mod registry;
+pub mod search;
 pub mod store;
+
+#[cfg(test)]
+mod bench;
+#[cfg(test)]
+mod tests;
@@ -6,6 +6,12 @@ pub struct LogItem {
     pub id: String,
     pub created_at: DateTime<Local>,
     pub text: String,
+    /// 코드 블록으로 붙인 텍스트 (터미널 출력 등)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+    /// 붙여 둔 터미널 녹화 파일 이름 (로그 디렉터리의 `casts/` 아래 asciicast)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cast: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -13,3 +19,12 @@ pub struct BranchLogFile {
     pub branch: String,
     pub items: Vec<LogItem>,
 }
+
+/// `gc`로 보관한 로그북 (gzip으로 압축해서 저장)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArchivedLogFile {
+    pub branch: String,
+    /// 브랜치가 없어진 것을 확인하고 보관한 시각
+    pub deleted_at: DateTime<Local>,
+    pub items: Vec<LogItem>,
+}
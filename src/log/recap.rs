@@ -0,0 +1,141 @@
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::log::model::LogItem;
+
+/// 브랜치로 돌아왔을 때 보여 줄 요약
+#[derive(Debug, Clone)]
+pub struct Recap {
+    pub branch: String,
+    /// 최근 로그, 최신순
+    pub recent: Vec<LogItem>,
+    /// 아직 끝나지 않은 할 일 중 최근 것, 오래된 순 (최근 로그와 같은 개수까지)
+    pub todos: Vec<LogItem>,
+    /// 끝나지 않은 할 일 전체 개수
+    pub open_todos: usize,
+    /// 마지막 로그 이후 지난 시간 (로그가 없으면 None)
+    pub since_last: Option<Duration>,
+}
+
+impl Recap {
+    pub fn build(branch: &str, items: &[LogItem], recent: usize, now: DateTime<Local>) -> Self {
+        let todos = items
+            .iter()
+            .filter(|item| is_open_todo(&item.text))
+            .collect::<Vec<_>>();
+        let since_last = items
+            .iter()
+            .map(|item| item.created_at)
+            .max()
+            .map(|last| now - last);
+        Self {
+            branch: branch.to_string(),
+            recent: items.iter().rev().take(recent).cloned().collect(),
+            todos: todos[todos.len().saturating_sub(recent)..]
+                .iter()
+                .map(|item| (*item).clone())
+                .collect(),
+            open_todos: todos.len(),
+            since_last,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.recent.is_empty() && self.todos.is_empty()
+    }
+}
+
+/// `TODO`, `할 일:`, `[ ]`, `- [ ]`로 시작하는 로그 (`[x]`로 바꾸면 끝난 것으로 본다)
+pub fn is_open_todo(text: &str) -> bool {
+    let text = text.trim_start();
+    let text = text
+        .strip_prefix("- ")
+        .or_else(|| text.strip_prefix("* "))
+        .unwrap_or(text);
+    if text.starts_with("[ ]") {
+        return true;
+    }
+    let head = text
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default();
+    head.eq_ignore_ascii_case("todo") || text.starts_with("할 일:") || text.starts_with("할일:")
+}
+
+/// 프롬프트용 브랜치 요약. `LogStore`가 기록할 때마다 따로 저장해 두어서
+/// 프롬프트는 전체 로그 JSON을 읽지 않는다.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchSummary {
+    pub count: usize,
+    pub open_todos: usize,
+    pub last_note_at: Option<DateTime<Local>>,
+}
+
+impl BranchSummary {
+    pub fn from_items(items: &[LogItem]) -> Self {
+        Self {
+            count: items.len(),
+            open_todos: items.iter().filter(|item| is_open_todo(&item.text)).count(),
+            last_note_at: items.iter().map(|item| item.created_at).max(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(minute: i64, text: &str) -> LogItem {
+        LogItem {
+            id: minute.to_string(),
+            created_at: Local::now() - Duration::minutes(60 - minute),
+            text: text.to_string(),
+            snippet: None,
+            cast: None,
+        }
+    }
+
+    #[test]
+    fn open_todo_markers() {
+        for text in [
+            "TODO: rebase",
+            "todo fix flaky test",
+            "  TODO",
+            "할 일: 리뷰 반영",
+            "할일: 배포",
+            "[ ] write docs",
+            "- [ ] bump version",
+            "* [ ] check CI",
+        ] {
+            assert!(is_open_todo(text), "{text}");
+        }
+        for text in [
+            "[x] write docs",
+            "- [x] bump version",
+            "todos are done",
+            "TODOLIST",
+            "fixed the TODO",
+            "할 일 목록 정리함",
+            "[] not a checkbox",
+        ] {
+            assert!(!is_open_todo(text), "{text}");
+        }
+    }
+
+    #[test]
+    fn todos_are_capped_like_recent_notes() {
+        let items = (0..10)
+            .map(|i| item(i, &format!("TODO {i}")))
+            .chain([item(20, "done")])
+            .collect::<Vec<_>>();
+        let recap = Recap::build("main", &items, 3, Local::now());
+        assert_eq!(recap.recent.len(), 3);
+        assert_eq!(recap.open_todos, 10);
+        let todos = recap
+            .todos
+            .iter()
+            .map(|t| t.text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(todos, ["TODO 7", "TODO 8", "TODO 9"]);
+    }
+}
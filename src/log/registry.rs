@@ -0,0 +1,176 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+use crate::log::model::LogItem;
+use crate::log::search::SearchHit;
+use crate::log::store::LogStore;
+
+const REGISTRY_FILENAME: &str = "repos.json";
+
+/// 로그북이 있는 repo 목록 (global.enabled일 때만 등록)
+#[derive(Debug)]
+pub struct Registry {
+    path: PathBuf,
+    repos: Vec<RegisteredRepo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisteredRepo {
+    pub root: PathBuf,
+    pub log_dir: PathBuf,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RegistryFile {
+    repos: Vec<RegisteredRepo>,
+}
+
+/// 여러 repo에서 모은 로그 한 건
+#[derive(Debug, Clone)]
+pub struct GlobalItem {
+    pub repo: PathBuf,
+    pub branch: String,
+    pub item: LogItem,
+}
+
+/// 여러 repo에서 찾은 검색 결과 한 건
+#[derive(Debug, Clone)]
+pub struct GlobalHit {
+    pub repo: PathBuf,
+    pub branch: String,
+    pub hit: SearchHit,
+}
+
+impl Registry {
+    pub fn load(data_dir: &Path) -> Result<Self, String> {
+        let path = data_dir.join(REGISTRY_FILENAME);
+        let repos = if path.exists() {
+            let data = fs::read_to_string(&path)
+                .map_err(|e| tr!("registry.read_failed", error = e, path = path.display()))?;
+            serde_json::from_str::<RegistryFile>(&data)
+                .map_err(|e| tr!("registry.parse_failed", error = e, path = path.display()))?
+                .repos
+        } else {
+            vec![]
+        };
+        Ok(Self { path, repos })
+    }
+
+    pub fn repos(&self) -> &[RegisteredRepo] {
+        &self.repos
+    }
+
+    /// 새 repo거나 로그 디렉터리가 바뀐 경우에만 파일에 기록
+    pub fn register(&mut self, root: &Path, log_dir: &Path) -> Result<(), String> {
+        let entry = RegisteredRepo {
+            root: root.to_path_buf(),
+            log_dir: log_dir.to_path_buf(),
+        };
+        match self.repos.iter_mut().find(|repo| repo.root == entry.root) {
+            Some(existing) if *existing == entry => return Ok(()),
+            Some(existing) => *existing = entry,
+            None => self.repos.push(entry),
+        }
+        self.repos.sort_by(|a, b| a.root.cmp(&b.root));
+        self.save()
+    }
+
+    /// repo나 로그 디렉터리가 사라진 항목을 지우고 지운 목록을 돌려준다.
+    pub fn prune(&mut self) -> Result<Vec<RegisteredRepo>, String> {
+        let (alive, stale): (Vec<_>, Vec<_>) = self
+            .repos
+            .drain(..)
+            .partition(|repo| repo.root.is_dir() && repo.log_dir.is_dir());
+        self.repos = alive;
+        if !stale.is_empty() {
+            self.save()?;
+        }
+        Ok(stale)
+    }
+
+    /// 등록된 모든 repo/브랜치의 로그를 시간순으로 모은다.
+    /// 읽지 못한 repo는 건너뛰고 (repo, 오류) 목록으로 따로 돌려준다.
+    pub fn collect(&self) -> (Vec<GlobalItem>, Vec<(PathBuf, String)>) {
+        let mut out = Vec::new();
+        let mut failed = Vec::new();
+        for repo in &self.repos {
+            match collect_repo(repo) {
+                Ok(items) => out.extend(items),
+                Err(e) => failed.push((repo.root.clone(), e)),
+            }
+        }
+        out.sort_by_key(|entry| entry.item.created_at);
+        (out, failed)
+    }
+
+    /// 등록된 모든 repo/브랜치를 `LogStore::search`로 찾아 점수순(같으면 최신순)으로 합친다.
+    /// 읽지 못한 repo는 `collect`처럼 따로 돌려준다.
+    pub fn search(&self, query: &str) -> (Vec<GlobalHit>, Vec<(PathBuf, String)>) {
+        let mut out = Vec::new();
+        let mut failed = Vec::new();
+        for repo in &self.repos {
+            match search_repo(repo, query) {
+                Ok(hits) => out.extend(hits),
+                Err(e) => failed.push((repo.root.clone(), e)),
+            }
+        }
+        out.sort_by(|a, b| {
+            b.hit
+                .score
+                .total_cmp(&a.hit.score)
+                .then_with(|| b.hit.item.created_at.cmp(&a.hit.item.created_at))
+        });
+        (out, failed)
+    }
+
+    fn save(&self) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(|e| tr!("registry.dir_create_failed", error = e))?;
+        }
+        let file = RegistryFile {
+            repos: self.repos.clone(),
+        };
+        let json = serde_json::to_string_pretty(&file)
+            .map_err(|e| tr!("registry.serialize_failed", error = e))?;
+        fs::write(&self.path, json).map_err(|e| {
+            tr!(
+                "registry.write_failed",
+                error = e,
+                path = self.path.display()
+            )
+        })
+    }
+}
+
+fn collect_repo(repo: &RegisteredRepo) -> Result<Vec<GlobalItem>, String> {
+    let store = LogStore::new(&repo.log_dir)?;
+    let mut out = Vec::new();
+    for branch in store.branches()? {
+        for item in store.list(&branch)? {
+            out.push(GlobalItem {
+                repo: repo.root.clone(),
+                branch: branch.clone(),
+                item,
+            });
+        }
+    }
+    Ok(out)
+}
+
+fn search_repo(repo: &RegisteredRepo, query: &str) -> Result<Vec<GlobalHit>, String> {
+    let store = LogStore::new(&repo.log_dir)?;
+    let mut out = Vec::new();
+    for branch in store.branches()? {
+        for hit in store.search(&branch, query)? {
+            out.push(GlobalHit {
+                repo: repo.root.clone(),
+                branch: branch.clone(),
+                hit,
+            });
+        }
+    }
+    Ok(out)
+}
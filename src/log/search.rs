@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::log::model::LogItem;
+
+/// 토큰 하나 (원문 byte 범위와 정규화된 검색어)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub term: String,
+    pub range: Range<usize>,
+}
+
+/// 검색 결과 한 건. `highlights`는 `item.text` 기준 byte 범위.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub item: LogItem,
+    pub score: f32,
+    pub highlights: Vec<Range<usize>>,
+}
+
+/// 브랜치 하나의 역색인 (정규화된 term → 로그 id별 등장 횟수)
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    postings: HashMap<String, HashMap<String, u32>>,
+    docs: HashMap<String, LogItem>,
+}
+
+impl SearchIndex {
+    pub fn build(items: &[LogItem]) -> Self {
+        let mut index = Self::default();
+        for item in items {
+            index.insert(item);
+        }
+        index
+    }
+
+    pub fn insert(&mut self, item: &LogItem) {
+        self.remove(&item.id);
+        for term in indexed_terms(item) {
+            *self
+                .postings
+                .entry(term)
+                .or_default()
+                .entry(item.id.clone())
+                .or_default() += 1;
+        }
+        self.docs.insert(item.id.clone(), item.clone());
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        let Some(item) = self.docs.remove(id) else {
+            return;
+        };
+        for term in indexed_terms(&item) {
+            if let Some(docs) = self.postings.get_mut(&term) {
+                docs.remove(id);
+                if docs.is_empty() {
+                    self.postings.remove(&term);
+                }
+            }
+        }
+    }
+
+    /// 모든 검색어가 (오타 허용으로) 맞는 로그를 점수순, 같으면 최신순으로 돌려준다.
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let terms = tokenize(query)
+            .into_iter()
+            .map(|token| token.term)
+            .collect::<Vec<_>>();
+        if terms.is_empty() {
+            return vec![];
+        }
+
+        let total = self.docs.len() as f32;
+        // 로그 id → 검색어별 최고 점수
+        let mut scores: HashMap<&str, Vec<f32>> = HashMap::new();
+        for (qi, query_term) in terms.iter().enumerate() {
+            for (term, docs) in &self.postings {
+                let quality = match_quality(query_term, term);
+                if quality == 0.0 {
+                    continue;
+                }
+                let idf = (1.0 + total / docs.len() as f32).ln();
+                for (id, count) in docs {
+                    let score = quality * idf * (1.0 + (*count as f32).ln());
+                    let slot = scores
+                        .entry(id.as_str())
+                        .or_insert_with(|| vec![0.0; terms.len()]);
+                    slot[qi] = slot[qi].max(score);
+                }
+            }
+        }
+
+        let mut hits = scores
+            .into_iter()
+            .filter(|(_, per_term)| per_term.iter().all(|s| *s > 0.0))
+            .filter_map(|(id, per_term)| {
+                let item = self.docs.get(id)?.clone();
+                let highlights = highlight(&item.text, &terms);
+                Some(SearchHit {
+                    item,
+                    score: per_term.iter().sum(),
+                    highlights,
+                })
+            })
+            .collect::<Vec<_>>();
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| b.item.created_at.cmp(&a.item.created_at))
+        });
+        hits
+    }
+}
+
+/// 로그 본문과 붙인 코드 블록의 term (코드 블록 안의 명령이나 오류 문구로도 찾을 수 있게)
+fn indexed_terms(item: &LogItem) -> impl Iterator<Item = String> + '_ {
+    let snippet = item.snippet.as_deref().unwrap_or_default();
+    tokenize(&item.text)
+        .into_iter()
+        .chain(tokenize(snippet))
+        .map(|token| token.term)
+}
+
+/// 글자/숫자 연속 구간을 토큰으로 나눈다. 한글 음절은 자모로 풀어서 정규화한다.
+pub fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (idx, ch) in text.char_indices() {
+        let is_word = ch.is_alphanumeric() || is_jamo(ch);
+        match (is_word, start) {
+            (true, None) => start = Some(idx),
+            (false, Some(s)) => {
+                tokens.push(make_token(text, s..idx));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        tokens.push(make_token(text, s..text.len()));
+    }
+    tokens
+}
+
+fn make_token(text: &str, range: Range<usize>) -> Token {
+    Token {
+        term: normalize(&text[range.clone()]),
+        range,
+    }
+}
+
+/// 소문자화 + 한글 음절을 호환 자모로 분해 (겹받침/겹모음도 나눔).
+/// 입력 중인 "테스ㅌ"나 "고"(→ "과")도 앞부분 일치로 찾을 수 있다.
+pub fn normalize(word: &str) -> String {
+    let mut out = String::with_capacity(word.len() * 3);
+    for ch in word.chars() {
+        if let Some(jamo) = decompose(ch) {
+            out.push_str(&jamo);
+        } else if let Some(split) = split_compound(ch) {
+            out.push_str(split);
+        } else {
+            out.extend(ch.to_lowercase());
+        }
+    }
+    out
+}
+
+const SYLLABLE_BASE: u32 = 0xAC00;
+const SYLLABLE_LAST: u32 = 0xD7A3;
+const CHOSEONG: [char; 19] = [
+    'ㄱ', 'ㄲ', 'ㄴ', 'ㄷ', 'ㄸ', 'ㄹ', 'ㅁ', 'ㅂ', 'ㅃ', 'ㅅ', 'ㅆ', 'ㅇ', 'ㅈ', 'ㅉ', 'ㅊ', 'ㅋ',
+    'ㅌ', 'ㅍ', 'ㅎ',
+];
+const JUNGSEONG: [&str; 21] = [
+    "ㅏ", "ㅐ", "ㅑ", "ㅒ", "ㅓ", "ㅔ", "ㅕ", "ㅖ", "ㅗ", "ㅗㅏ", "ㅗㅐ", "ㅗㅣ", "ㅛ", "ㅜ",
+    "ㅜㅓ", "ㅜㅔ", "ㅜㅣ", "ㅠ", "ㅡ", "ㅡㅣ", "ㅣ",
+];
+const JONGSEONG: [&str; 28] = [
+    "", "ㄱ", "ㄲ", "ㄱㅅ", "ㄴ", "ㄴㅈ", "ㄴㅎ", "ㄷ", "ㄹ", "ㄹㄱ", "ㄹㅁ", "ㄹㅂ", "ㄹㅅ",
+    "ㄹㅌ", "ㄹㅍ", "ㄹㅎ", "ㅁ", "ㅂ", "ㅂㅅ", "ㅅ", "ㅆ", "ㅇ", "ㅈ", "ㅊ", "ㅋ", "ㅌ", "ㅍ",
+    "ㅎ",
+];
+
+fn decompose(ch: char) -> Option<String> {
+    let code = ch as u32;
+    if !(SYLLABLE_BASE..=SYLLABLE_LAST).contains(&code) {
+        return None;
+    }
+    let idx = code - SYLLABLE_BASE;
+    let mut out = String::new();
+    out.push(CHOSEONG[(idx / 588) as usize]);
+    out.push_str(JUNGSEONG[((idx % 588) / 28) as usize]);
+    out.push_str(JONGSEONG[(idx % 28) as usize]);
+    Some(out)
+}
+
+/// 단독으로 입력된 겹자모 (예: "ㅘ", "ㄳ")
+fn split_compound(ch: char) -> Option<&'static str> {
+    JUNGSEONG
+        .iter()
+        .chain(JONGSEONG.iter())
+        .find(|jamo| jamo.chars().count() == 2 && compose_pair(jamo) == Some(ch))
+        .copied()
+}
+
+fn compose_pair(pair: &str) -> Option<char> {
+    let compound = match pair {
+        "ㅗㅏ" => 'ㅘ',
+        "ㅗㅐ" => 'ㅙ',
+        "ㅗㅣ" => 'ㅚ',
+        "ㅜㅓ" => 'ㅝ',
+        "ㅜㅔ" => 'ㅞ',
+        "ㅜㅣ" => 'ㅟ',
+        "ㅡㅣ" => 'ㅢ',
+        "ㄱㅅ" => 'ㄳ',
+        "ㄴㅈ" => 'ㄵ',
+        "ㄴㅎ" => 'ㄶ',
+        "ㄹㄱ" => 'ㄺ',
+        "ㄹㅁ" => 'ㄻ',
+        "ㄹㅂ" => 'ㄼ',
+        "ㄹㅅ" => 'ㄽ',
+        "ㄹㅌ" => 'ㄾ',
+        "ㄹㅍ" => 'ㄿ',
+        "ㄹㅎ" => 'ㅀ',
+        "ㅂㅅ" => 'ㅄ',
+        _ => return None,
+    };
+    Some(compound)
+}
+
+/// 한글 호환 자모 (ㄱ-ㅣ)
+fn is_jamo(ch: char) -> bool {
+    ('\u{3131}'..='\u{3163}').contains(&ch)
+}
+
+/// 검색어가 term과 얼마나 맞는지 (0이면 불일치).
+/// 완전 일치 > 앞부분 일치 > 오타 허용 일치 순.
+pub fn match_quality(query: &str, term: &str) -> f32 {
+    if query == term {
+        return 1.0;
+    }
+    if term.starts_with(query) {
+        return 0.6 + 0.3 * query.chars().count() as f32 / term.chars().count() as f32;
+    }
+
+    // 짧은 검색어는 오타 허용 시 엉뚱한 결과가 많아서 제외
+    let query_len = query.chars().count();
+    let allowed = match query_len {
+        0..=2 => return 0.0,
+        3..=7 => 1,
+        _ => 2,
+    };
+    // 첫 글자(한글은 초성)는 맞아야 한다. 색인의 대부분을 편집 거리 계산 없이 걸러낸다.
+    if query.chars().next() != term.chars().next() || term.chars().count() + allowed < query_len {
+        return 0.0;
+    }
+    let query_chars = query.chars().collect::<Vec<_>>();
+    let term_chars = term.chars().collect::<Vec<_>>();
+    let prefix_len = query_chars.len().min(term_chars.len());
+    let distance = edit_distance(&query_chars, &term_chars)
+        .min(edit_distance(&query_chars, &term_chars[..prefix_len]));
+    if distance > allowed {
+        return 0.0;
+    }
+    0.5 * (1.0 - distance as f32 / (allowed + 1) as f32)
+}
+
+/// 인접 문자 교환을 1회로 치는 편집 거리 (optimal string alignment)
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut prev2 = vec![0; b.len() + 1];
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut cur = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        cur[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            cur[j] = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cur[j] = cur[j].min(prev2[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut prev2, &mut prev);
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// 검색어와 맞는 토큰의 byte 범위
+pub fn highlight(text: &str, terms: &[String]) -> Vec<Range<usize>> {
+    tokenize(text)
+        .into_iter()
+        .filter(|token| terms.iter().any(|q| match_quality(q, &token.term) > 0.0))
+        .map(|token| token.range)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, minute: u32, text: &str) -> LogItem {
+        use chrono::TimeZone;
+        LogItem {
+            id: id.to_string(),
+            created_at: chrono::Local
+                .with_ymd_and_hms(2026, 1, 1, 0, minute, 0)
+                .unwrap(),
+            text: text.to_string(),
+            snippet: None,
+            cast: None,
+        }
+    }
+
+    fn ids(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|hit| hit.item.id.as_str()).collect()
+    }
+
+    #[test]
+    fn hangul_decomposes_into_jamo() {
+        assert_eq!(normalize("한글"), "ㅎㅏㄴㄱㅡㄹ");
+        assert_eq!(normalize("과"), "ㄱㅗㅏ");
+        assert_eq!(normalize("닭"), "ㄷㅏㄹㄱ");
+        assert_eq!(normalize("ㅘ"), "ㅗㅏ");
+        assert_eq!(normalize("Rust"), "rust");
+
+        let tokens = tokenize("빌드 실패, 테스ㅌ!");
+        let terms = tokens.iter().map(|t| t.term.as_str()).collect::<Vec<_>>();
+        assert_eq!(terms, ["ㅂㅣㄹㄷㅡ", "ㅅㅣㄹㅍㅐ", "ㅌㅔㅅㅡㅌ"]);
+        assert_eq!(tokens[1].range, 7..13);
+        // 입력 중인 음절도 앞부분 일치
+        assert!(match_quality(&normalize("고"), &normalize("과제")) > 0.0);
+    }
+
+    #[test]
+    fn typo_tolerance_depends_on_query_length() {
+        assert_eq!(edit_distance(&['a', 'b'], &['b', 'a']), 1);
+        // 두 글자 이하는 오타를 허용하지 않는다
+        assert_eq!(match_quality("ab", "ac"), 0.0);
+        // 3~7글자는 한 글자, 8글자부터 두 글자
+        assert!(match_quality("tset", "test") > 0.0);
+        assert_eq!(match_quality("tsta", "test"), 0.0);
+        assert!(match_quality("migratoin", "migration") > 0.0);
+        assert!(match_quality("mgiratoin", "migration") > 0.0);
+        assert_eq!(match_quality("mgiratoni", "migration"), 0.0);
+        // 첫 글자가 다르면 오타로 보지 않는다
+        assert_eq!(match_quality("best", "test"), 0.0);
+    }
+
+    #[test]
+    fn exact_beats_prefix_beats_typo() {
+        let exact = match_quality("build", "build");
+        let prefix = match_quality("build", "builder");
+        let typo = match_quality("biuld", "build");
+        assert!(exact > prefix && prefix > typo && typo > 0.0);
+
+        let index = SearchIndex::build(&[
+            item("typo", 3, "biuld script"),
+            item("prefix", 2, "builder notes"),
+            item("exact", 1, "build failed"),
+        ]);
+        assert_eq!(ids(&index.search("build")), ["exact", "prefix", "typo"]);
+        // 모든 검색어가 맞아야 한다
+        assert_eq!(ids(&index.search("build failed")), ["exact"]);
+    }
+
+    #[test]
+    fn snippets_are_searchable_and_removed_with_the_note() {
+        let mut noted = item("1", 0, "CI repro");
+        noted.snippet = Some("error[E0308]: mismatched types".to_string());
+        let mut index = SearchIndex::build(&[noted, item("2", 1, "other")]);
+
+        let hits = index.search("mismatched");
+        assert_eq!(ids(&hits), ["1"]);
+        // 강조 범위는 본문 기준이라 코드 블록에서만 맞으면 비어 있다
+        assert!(hits[0].highlights.is_empty());
+
+        index.remove("1");
+        assert!(index.search("mismatched").is_empty());
+        assert!(index.postings.keys().all(|term| term != "mismatched"));
+    }
+}
@@ -1,111 +1,459 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::SystemTime;
 
+use crate::i18n::tr;
 use crate::log::model::{BranchLogFile, LogItem};
+use crate::log::recap::BranchSummary;
+use crate::log::search::{SearchHit, SearchIndex};
 use chrono::Local;
 
+/// 브랜치별 요약 파일을 두는 하위 디렉터리 (로그 파일 목록에 섞이지 않게 분리)
+const SUMMARY_DIRNAME: &str = ".summary";
+/// 터미널 녹화 파일을 두는 하위 디렉터리
+const CAST_DIRNAME: &str = "casts";
+/// 파일 이름을 `branch_file_stem` 방식으로 옮겼다는 표시 (이전에는 `/`를 `__`로 바꿈)
+const NAMES_MARKER: &str = ".branch-names-v2";
+
 #[derive(Debug)]
 pub struct LogStore {
-    base_dir: PathBuf, // repo_root/.bbiribarabu/logs
+    base_dir: PathBuf, // 기본값: repo_root/.bbiribarabu/logs (storage.dir)
+    // 브랜치별로 읽어 둔 로그. 파일이 바뀌면(다른 프로세스가 기록) 다시 읽는다.
+    cache: Mutex<HashMap<String, CachedBranch>>,
+}
+
+/// 파일 변경 감지용 (수정 시각, 크기). 파일이 없으면 None.
+type Stamp = Option<(SystemTime, u64)>;
+
+#[derive(Debug)]
+struct CachedBranch {
+    stamp: Stamp,
+    // 기록하려고 꺼내 간 동안은 None (기록이 실패하면 파일에서 다시 읽는다)
+    items: Option<Arc<Vec<LogItem>>>,
+    // 첫 검색 때 만든다
+    index: Option<SearchIndex>,
 }
 
 impl LogStore {
-    pub fn new(repo_root: &Path) -> Result<Self, String> {
-        let base_dir = repo_root.join(".bbiribarabu").join("logs");
-        fs::create_dir_all(&base_dir).map_err(|e| format!("로그 디렉터리 생성 실패: {}", e))?;
+    pub fn new(base_dir: &Path) -> Result<Self, String> {
+        let base_dir = base_dir.to_path_buf();
+        fs::create_dir_all(&base_dir).map_err(|e| tr!("store.dir_create_failed", error = e))?;
+
+        let store = Self {
+            base_dir,
+            cache: Mutex::new(HashMap::new()),
+        };
+        store.migrate_file_names();
+        Ok(store)
+    }
+
+    /// `/`를 `__`로 바꾸던 예전 파일 이름을 파일 안의 브랜치 이름 기준으로 고친다.
+    /// (`fix__x`가 `fix/x`로 읽혀서 gc가 살아 있는 브랜치 로그를 지우지 않도록)
+    fn migrate_file_names(&self) {
+        let marker = self.base_dir.join(NAMES_MARKER);
+        if marker.exists() {
+            return;
+        }
+        let Ok(entries) = fs::read_dir(&self.base_dir) else {
+            return;
+        };
+        for path in entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+        {
+            let Some(stem) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .filter(|_| path.extension().is_some_and(|ext| ext == "json"))
+            else {
+                continue;
+            };
+            if !stem.contains("__") && !stem.contains('%') {
+                continue;
+            }
+            let Ok(file) = fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|data| {
+                    serde_json::from_str::<BranchLogFile>(&data).map_err(|e| e.to_string())
+                })
+            else {
+                continue;
+            };
+            let target = self.branch_file_path(&file.branch);
+            if file.branch.is_empty() || target == path || target.exists() {
+                continue;
+            }
+            if fs::rename(&path, &target).is_ok() {
+                let old_summary = self
+                    .base_dir
+                    .join(SUMMARY_DIRNAME)
+                    .join(format!("{stem}.json"));
+                let _ = fs::remove_file(old_summary);
+            }
+        }
+        let _ = fs::write(marker, "");
+    }
 
-        Ok(Self { base_dir })
+    /// 로그 파일이 있는 디렉터리
+    pub fn dir(&self) -> &Path {
+        &self.base_dir
     }
 
     fn branch_file_path(&self, branch: &str) -> PathBuf {
-        // 브랜치명에 슬래시가 들어가면 파일 경로 깨질 수 있어서 치환
-        let safe = branch.replace('/', "__");
-        self.base_dir.join(format!("{}.json", safe))
+        self.base_dir
+            .join(format!("{}.json", branch_file_stem(branch)))
     }
 
-    pub fn load(&self, branch: &str) -> Result<BranchLogFile, String> {
-        let path = self.branch_file_path(branch);
+    fn summary_path(&self, branch: &str) -> PathBuf {
+        self.base_dir
+            .join(SUMMARY_DIRNAME)
+            .join(format!("{}.json", branch_file_stem(branch)))
+    }
 
-        if !path.exists() {
-            return Ok(BranchLogFile {
-                branch: branch.to_string(),
-                items: vec![],
-            });
+    /// 로그 파일이 존재하는 브랜치 목록 (이름순)
+    pub fn branches(&self) -> Result<Vec<String>, String> {
+        let entries =
+            fs::read_dir(&self.base_dir).map_err(|e| tr!("store.dir_read_failed", error = e))?;
+
+        let mut branches = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| {
+                path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .map(branch_from_file_stem)
+            })
+            .collect::<Vec<_>>();
+        branches.sort();
+        Ok(branches)
+    }
+
+    /// 파일이 그대로면 메모리에 있는 로그를 그대로 돌려준다 (TUI는 매 프레임 호출).
+    pub fn snapshot(&self, branch: &str) -> Result<Arc<Vec<LogItem>>, String> {
+        let stamp = self.stamp(branch);
+        let mut cache = self.lock_cache();
+        if let Some(cached) = cache.get(branch)
+            && cached.stamp == stamp
+            && let Some(items) = &cached.items
+        {
+            return Ok(items.clone());
         }
 
-        let data = fs::read_to_string(&path)
-            .map_err(|e| format!("로그 파일 읽기 실패: {} ({})", e, path.display()))?;
+        let items = Arc::new(self.read_file(branch)?.items);
+        cache.insert(
+            branch.to_string(),
+            CachedBranch {
+                stamp,
+                items: Some(items.clone()),
+                index: None,
+            },
+        );
+        Ok(items)
+    }
 
-        serde_json::from_str(&data).map_err(|e| format!("로그 JSON 파싱 실패: {}", e))
+    /// 기록용으로 읽는다. 캐시에서 꺼내 오므로 화면 등에서 같은 스냅샷을 들고 있지 않으면
+    /// 복사하지 않는다. 캐시는 `commit`이 기록한 내용으로 다시 채운다.
+    fn load(&self, branch: &str) -> Result<BranchLogFile, String> {
+        let items = self.snapshot(branch)?;
+        if let Some(cached) = self.lock_cache().get_mut(branch) {
+            cached.items = None;
+        }
+        Ok(BranchLogFile {
+            branch: branch.to_string(),
+            items: Arc::unwrap_or_clone(items),
+        })
     }
 
     pub fn append_text(&self, branch: &str, text: &str) -> Result<LogItem, String> {
+        self.append(branch, text, None, None)
+    }
+
+    /// 설명(`text`)과 함께 코드 블록을 붙여 기록한다
+    pub fn append_snippet(
+        &self,
+        branch: &str,
+        text: &str,
+        snippet: &str,
+    ) -> Result<LogItem, String> {
+        self.append(branch, text, Some(snippet.to_string()), None)
+    }
+
+    /// 설명과 함께 터미널 녹화 파일을 붙여 기록한다 (다른 디렉터리에 있으면 옮겨 온다)
+    pub fn append_cast(&self, branch: &str, text: &str, cast: &Path) -> Result<LogItem, String> {
+        let name = self.import_cast(cast)?;
+        self.append(branch, text, None, Some(name))
+    }
+
+    /// 새 녹화 파일 경로 (`casts/<시각>.cast`)
+    pub fn new_cast_path(&self) -> Result<PathBuf, String> {
+        let dir = self.base_dir.join(CAST_DIRNAME);
+        fs::create_dir_all(&dir).map_err(|e| tr!("store.dir_create_failed", error = e))?;
+        Ok(dir.join(format!("{}.cast", Local::now().timestamp_millis())))
+    }
+
+    /// 로그에 붙은 녹화 파일 경로
+    pub fn cast_path(&self, name: &str) -> PathBuf {
+        self.base_dir.join(CAST_DIRNAME).join(name)
+    }
+
+    /// 녹화 파일을 이 저장소의 `casts/`로 옮기고 파일 이름을 돌려준다
+    pub fn import_cast(&self, path: &Path) -> Result<String, String> {
+        let (name, target) = self.cast_target(path)?;
+        // 파일 시스템이 다르면 rename이 안 되므로 복사 후 지운다
+        if target != path && fs::rename(path, &target).is_err() {
+            fs::copy(path, &target)
+                .map_err(|e| tr!("store.write_failed", error = e, path = target.display()))?;
+            let _ = fs::remove_file(path);
+        }
+        Ok(name)
+    }
+
+    /// 녹화 파일을 이 저장소의 `casts/`로 복사하고 파일 이름을 돌려준다 (원본은 남긴다)
+    pub fn copy_cast(&self, path: &Path) -> Result<String, String> {
+        let (name, target) = self.cast_target(path)?;
+        if target != path {
+            fs::copy(path, &target)
+                .map_err(|e| tr!("store.write_failed", error = e, path = target.display()))?;
+        }
+        Ok(name)
+    }
+
+    fn cast_target(&self, path: &Path) -> Result<(String, PathBuf), String> {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| format!("{}.cast", Local::now().timestamp_millis()));
+        let target = self.cast_path(&name);
+        if target != path {
+            fs::create_dir_all(self.base_dir.join(CAST_DIRNAME))
+                .map_err(|e| tr!("store.dir_create_failed", error = e))?;
+        }
+        Ok((name, target))
+    }
+
+    /// 녹화 파일 삭제 (없으면 무시)
+    pub fn remove_cast(&self, name: &str) {
+        let _ = fs::remove_file(self.cast_path(name));
+    }
+
+    fn append(
+        &self,
+        branch: &str,
+        text: &str,
+        snippet: Option<String>,
+        cast: Option<String>,
+    ) -> Result<LogItem, String> {
         let mut file = self.load(branch)?;
         let item = LogItem {
             id: format!("{}", Local::now().timestamp_millis()),
             created_at: Local::now(),
             text: text.to_string(),
+            snippet,
+            cast,
         };
         file.items.push(item.clone());
 
-        let path = self.branch_file_path(branch);
-        let json = serde_json::to_string_pretty(&file)
-            .map_err(|e| format!("로그 JSON 직렬화 실패: {}", e))?;
-
-        fs::write(&path, json)
-            .map_err(|e| format!("로그 파일 쓰기 실패: {} ({})", e, path.display()))?;
+        self.commit(branch, file, |index| index.insert(&item))?;
 
         Ok(item)
     }
 
+    /// 다른 로그북에서 옮겨 온 항목을 시간순으로 합친다.
+    pub fn append_items(&self, branch: &str, items: &[LogItem]) -> Result<(), String> {
+        let mut file = self.load(branch)?;
+        file.items.extend(items.iter().cloned());
+        file.items.sort_by_key(|item| item.created_at);
+        self.commit(branch, file, |index| {
+            for item in items {
+                index.insert(item);
+            }
+        })
+    }
+
+    /// 개수/할 일/마지막 시각 요약. 요약 파일이 로그 파일보다 오래됐으면
+    /// (다른 버전이나 직접 편집으로 기록된 경우) 로그를 한 번 읽어서 다시 만든다.
+    pub fn summary(&self, branch: &str) -> Result<BranchSummary, String> {
+        let modified = |path: &Path| fs::metadata(path).and_then(|meta| meta.modified()).ok();
+        let Some(log_modified) = modified(&self.branch_file_path(branch)) else {
+            return Ok(BranchSummary::default());
+        };
+        let summary_path = self.summary_path(branch);
+        if modified(&summary_path).is_some_and(|at| at >= log_modified)
+            && let Ok(data) = fs::read(&summary_path)
+            && let Ok(summary) = serde_json::from_slice(&data)
+        {
+            return Ok(summary);
+        }
+
+        let summary = BranchSummary::from_items(&self.read_file(branch)?.items);
+        // 요약은 캐시일 뿐이라 저장 실패는 무시한다
+        let _ = self.write_summary(branch, &summary);
+        Ok(summary)
+    }
+
+    /// 브랜치 로그 파일 삭제 (없으면 아무것도 하지 않음)
+    pub fn remove_branch(&self, branch: &str) -> Result<(), String> {
+        let path = self.branch_file_path(branch);
+        self.lock_cache().remove(branch);
+        let _ = fs::remove_file(self.summary_path(branch));
+        if !path.exists() {
+            return Ok(());
+        }
+        fs::remove_file(&path)
+            .map_err(|e| tr!("store.write_failed", error = e, path = path.display()))
+    }
+
+    /// 검색어와 맞는 로그를 관련도순으로 (한글 자모 단위 오타/부분 입력 허용)
+    pub fn search(&self, branch: &str, query: &str) -> Result<Vec<SearchHit>, String> {
+        let items = self.snapshot(branch)?;
+        let mut cache = self.lock_cache();
+        let Some(cached) = cache.get_mut(branch) else {
+            return Ok(SearchIndex::build(&items).search(query));
+        };
+        Ok(cached
+            .index
+            .get_or_insert_with(|| SearchIndex::build(&items))
+            .search(query))
+    }
+
     pub fn list(&self, branch: &str) -> Result<Vec<LogItem>, String> {
-        Ok(self.load(branch)?.items)
+        Ok(self.snapshot(branch)?.to_vec())
     }
 
     pub fn delete_by_id(&self, branch: &str, id: &str) -> Result<bool, String> {
         let mut file = self.load(branch)?;
-        let before = file.items.len();
-        file.items.retain(|item| item.id != id);
-        if file.items.len() == before {
+        let Some(pos) = file.items.iter().position(|item| item.id == id) else {
             return Ok(false);
-        }
-
-        let path = self.branch_file_path(branch);
-        let json = serde_json::to_string_pretty(&file)
-            .map_err(|e| format!("로그 JSON 직렬화 실패: {}", e))?;
+        };
+        let removed = file.items.remove(pos);
 
-        fs::write(&path, json)
-            .map_err(|e| format!("로그 파일 쓰기 실패: {} ({})", e, path.display()))?;
+        self.commit(branch, file, |index| index.remove(id))?;
+        if let Some(cast) = &removed.cast {
+            self.remove_cast(cast);
+        }
 
         Ok(true)
     }
 
-    pub fn update_text_by_id(
-        &self,
-        branch: &str,
-        id: &str,
-        text: &str,
-    ) -> Result<bool, String> {
+    pub fn update_text_by_id(&self, branch: &str, id: &str, text: &str) -> Result<bool, String> {
         let mut file = self.load(branch)?;
-        let mut found = false;
-        for item in &mut file.items {
-            if item.id == id {
-                item.text = text.to_string();
-                found = true;
-                break;
-            }
-        }
-        if !found {
+        let Some(item) = file.items.iter_mut().find(|item| item.id == id) else {
             return Ok(false);
+        };
+        item.text = text.to_string();
+        let item = item.clone();
+
+        self.commit(branch, file, |index| index.insert(&item))?;
+
+        Ok(true)
+    }
+
+    fn read_file(&self, branch: &str) -> Result<BranchLogFile, String> {
+        let path = self.branch_file_path(branch);
+
+        if !path.exists() {
+            return Ok(BranchLogFile {
+                branch: branch.to_string(),
+                items: vec![],
+            });
         }
 
+        let data = fs::read_to_string(&path)
+            .map_err(|e| tr!("store.read_failed", error = e, path = path.display()))?;
+
+        serde_json::from_str(&data).map_err(|e| tr!("store.parse_failed", error = e))
+    }
+
+    /// 파일에 기록하고, 다시 읽지 않도록 기록한 내용으로 캐시와 색인을 갱신한다.
+    fn commit(
+        &self,
+        branch: &str,
+        file: BranchLogFile,
+        update_index: impl FnOnce(&mut SearchIndex),
+    ) -> Result<(), String> {
         let path = self.branch_file_path(branch);
         let json = serde_json::to_string_pretty(&file)
-            .map_err(|e| format!("로그 JSON 직렬화 실패: {}", e))?;
+            .map_err(|e| tr!("store.serialize_failed", error = e))?;
 
         fs::write(&path, json)
-            .map_err(|e| format!("로그 파일 쓰기 실패: {} ({})", e, path.display()))?;
+            .map_err(|e| tr!("store.write_failed", error = e, path = path.display()))?;
+        let _ = self.write_summary(branch, &BranchSummary::from_items(&file.items));
 
-        Ok(true)
+        let stamp = self.stamp(branch);
+        let mut cache = self.lock_cache();
+        // 색인은 방금 읽은 캐시 기준이라 변경분만 반영하면 된다
+        let mut index = cache.remove(branch).and_then(|cached| cached.index);
+        if let Some(index) = index.as_mut() {
+            update_index(index);
+        }
+        cache.insert(
+            branch.to_string(),
+            CachedBranch {
+                stamp,
+                items: Some(Arc::new(file.items)),
+                index,
+            },
+        );
+        Ok(())
+    }
+
+    fn write_summary(&self, branch: &str, summary: &BranchSummary) -> Result<(), String> {
+        let path = self.summary_path(branch);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| tr!("store.dir_create_failed", error = e))?;
+        }
+        let json =
+            serde_json::to_vec(summary).map_err(|e| tr!("store.serialize_failed", error = e))?;
+        fs::write(&path, json)
+            .map_err(|e| tr!("store.write_failed", error = e, path = path.display()))
+    }
+
+    /// 감시기가 바뀌었다고 알린 로그 파일의 캐시를 버린다. 크기가 같고 수정 시각이 같은
+    /// 틱에 들어간 변경은 stamp로 알 수 없어서 다음 `snapshot`이 파일을 다시 읽게 한다.
+    pub fn forget_file(&self, path: &Path) {
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            return;
+        };
+        self.lock_cache().remove(&branch_from_file_stem(stem));
+    }
+
+    fn stamp(&self, branch: &str) -> Stamp {
+        let meta = fs::metadata(self.branch_file_path(branch)).ok()?;
+        Some((meta.modified().ok()?, meta.len()))
+    }
+
+    fn lock_cache(&self) -> MutexGuard<'_, HashMap<String, CachedBranch>> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 브랜치 이름 → 파일 이름. 슬래시가 경로를 깨지 않도록 `/`와 `%`만 퍼센트 인코딩해서
+/// 다시 풀 수 있게 한다 (`feature/x` → `feature%2Fx`).
+pub fn branch_file_stem(branch: &str) -> String {
+    branch.replace('%', "%25").replace('/', "%2F")
+}
+
+/// `branch_file_stem`을 되돌린다. 모르는 `%` 조합은 그대로 둔다.
+pub fn branch_from_file_stem(stem: &str) -> String {
+    let mut out = String::with_capacity(stem.len());
+    let mut rest = stem;
+    while let Some(pos) = rest.find('%') {
+        out.push_str(&rest[..pos]);
+        let escape = &rest[pos..];
+        if escape.starts_with("%2F") {
+            out.push('/');
+        } else if escape.starts_with("%25") {
+            out.push('%');
+        } else {
+            out.push('%');
+            rest = &escape[1..];
+            continue;
+        }
+        rest = &escape[3..];
     }
+    out.push_str(rest);
+    out
 }
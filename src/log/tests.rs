@@ -0,0 +1,183 @@
+//! 임시 디렉터리에 만든 로그북으로 `LogStore` 캐시, 파일 이름, gc 보관, 전역 검색을 확인한다.
+
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::Read;
+use std::sync::Arc;
+
+use chrono::Local;
+use flate2::read::GzDecoder;
+
+use crate::log::archive;
+use crate::log::model::{ArchivedLogFile, BranchLogFile};
+use crate::log::registry::Registry;
+use crate::log::store::{LogStore, branch_file_stem, branch_from_file_stem};
+
+#[test]
+fn writes_reuse_the_cached_snapshot() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = LogStore::new(dir.path()).unwrap();
+    store.append_text("main", "first").unwrap();
+    let before = store.snapshot("main").unwrap();
+
+    store.append_text("main", "second").unwrap();
+    // 화면이 들고 있던 스냅샷은 그대로고, 캐시는 기록한 내용으로 바뀐다
+    assert_eq!(before.len(), 1);
+    let after = store.snapshot("main").unwrap();
+    assert_eq!(after.len(), 2);
+    assert!(Arc::ptr_eq(&after, &store.snapshot("main").unwrap()));
+    assert_eq!(store.search("main", "second").unwrap().len(), 1);
+
+    // 없는 id라 기록하지 않아도 캐시는 파일에서 다시 채워진다
+    assert!(!store.delete_by_id("main", "missing").unwrap());
+    assert_eq!(store.list("main").unwrap().len(), 2);
+}
+
+#[test]
+fn forgetting_a_file_rereads_same_size_edits() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = LogStore::new(dir.path()).unwrap();
+    store.append_text("feature/x", "first").unwrap();
+    assert_eq!(store.snapshot("feature/x").unwrap()[0].text, "first");
+
+    // 다른 프로세스가 같은 크기로 고쳤고 수정 시각도 그대로인 경우
+    let path = dir
+        .path()
+        .join(format!("{}.json", branch_file_stem("feature/x")));
+    let modified = fs::metadata(&path).unwrap().modified().unwrap();
+    let json = fs::read_to_string(&path).unwrap().replace("first", "fixed");
+    fs::write(&path, json).unwrap();
+    File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+    assert_eq!(store.snapshot("feature/x").unwrap()[0].text, "first");
+
+    store.forget_file(&path);
+    assert_eq!(store.snapshot("feature/x").unwrap()[0].text, "fixed");
+}
+
+#[test]
+fn branch_file_names_round_trip() {
+    for branch in ["main", "feature/x", "fix__x", "a%2Fb", "100%", "%/%25"] {
+        let stem = branch_file_stem(branch);
+        assert!(!stem.contains('/'));
+        assert_eq!(branch_from_file_stem(&stem), branch);
+    }
+    assert_eq!(branch_file_stem("feature/x"), "feature%2Fx");
+}
+
+#[test]
+fn legacy_file_names_are_migrated_by_their_branch_field() {
+    let dir = tempfile::tempdir().unwrap();
+    let write = |stem: &str, branch: &str| {
+        let file = BranchLogFile {
+            branch: branch.to_string(),
+            items: vec![],
+        };
+        let json = serde_json::to_string(&file).unwrap();
+        fs::write(dir.path().join(format!("{stem}.json")), json).unwrap();
+    };
+    write("feature__x", "feature/x");
+    write("fix__x", "fix__x");
+    write("main", "main");
+
+    let store = LogStore::new(dir.path()).unwrap();
+    assert_eq!(store.branches().unwrap(), ["feature/x", "fix__x", "main"]);
+    assert!(dir.path().join("feature%2Fx.json").exists());
+    assert!(!dir.path().join("feature__x.json").exists());
+}
+
+#[test]
+fn orphans_are_only_branches_missing_from_live() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = LogStore::new(dir.path()).unwrap();
+    for branch in ["fix__x", "feature/y", "gone/z"] {
+        store.append_text(branch, "note").unwrap();
+    }
+    let live = ["fix__x", "feature/y", "main"]
+        .map(str::to_string)
+        .into_iter()
+        .collect::<HashSet<_>>();
+    assert_eq!(archive::orphaned(&store, &live).unwrap(), ["gone/z"]);
+}
+
+#[test]
+fn archive_keeps_notes_and_removes_the_logbook() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = LogStore::new(dir.path()).unwrap();
+    store.append_text("gone/z", "first").unwrap();
+    store
+        .append_snippet("gone/z", "second", "cargo test")
+        .unwrap();
+    let notes = store.list("gone/z").unwrap();
+
+    let deleted_at = Local::now();
+    let path = archive::archive(&store, "gone/z", deleted_at).unwrap();
+    assert!(path.starts_with(dir.path().join(archive::ARCHIVE_DIRNAME)));
+    assert!(store.branches().unwrap().is_empty());
+
+    let mut json = String::new();
+    GzDecoder::new(File::open(&path).unwrap())
+        .read_to_string(&mut json)
+        .unwrap();
+    let archived: ArchivedLogFile = serde_json::from_str(&json).unwrap();
+    assert_eq!(archived.branch, "gone/z");
+    assert_eq!(archived.deleted_at, deleted_at);
+    let texts = |items: &[crate::log::model::LogItem]| {
+        items
+            .iter()
+            .map(|item| (item.id.clone(), item.text.clone(), item.snippet.clone()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(texts(&archived.items), texts(&notes));
+}
+
+#[test]
+fn global_search_ranks_hits_across_repos() {
+    let data = tempfile::tempdir().unwrap();
+    let first = tempfile::tempdir().unwrap();
+    let second = tempfile::tempdir().unwrap();
+    let mut registry = Registry::load(data.path()).unwrap();
+    for repo in [&first, &second] {
+        registry
+            .register(repo.path(), &repo.path().join("logs"))
+            .unwrap();
+    }
+    LogStore::new(&first.path().join("logs"))
+        .unwrap()
+        .append_text("main", "build failed")
+        .unwrap();
+    let store = LogStore::new(&second.path().join("logs")).unwrap();
+    store.append_text("feature/x", "biuld script").unwrap();
+    store.append_text("feature/x", "빌드 실패").unwrap();
+
+    // 오타 허용 결과도 찾고, 정확히 맞는 쪽이 다른 repo여도 먼저 온다
+    let (hits, failed) = registry.search("build");
+    assert!(failed.is_empty());
+    let found = hits
+        .iter()
+        .map(|entry| {
+            (
+                entry.repo.as_path(),
+                entry.branch.as_str(),
+                entry.hit.item.text.as_str(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        [
+            (first.path(), "main", "build failed"),
+            (second.path(), "feature/x", "biuld script"),
+        ]
+    );
+    assert_eq!(hits[0].hit.highlights, [0..5]);
+
+    // 입력 중인 음절로도 찾는다
+    let (hits, _) = registry.search("빌");
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].hit.item.text, "빌드 실패");
+}
@@ -1,109 +1,616 @@
 mod app;
 mod cli;
+mod complete;
+mod config;
 mod git;
+mod i18n;
 mod log;
+mod prompt;
 mod ui;
 mod voice;
 
+use std::io::IsTerminal;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
 use app::AppState;
-use clap::Parser;
-use cli::{Cli, Commands};
+use chrono::Local;
+use clap::FromArgMatches;
+use cli::{Cli, Commands, ConfigAction, GlobalAction};
+use config::{ConfigLayers, ConfigScope};
+use i18n::tr;
+use log::registry::Registry;
 use voice::silence_whisper_logs;
 
 fn main() {
+    // git 탐색과 설정 읽기는 여기서 한 번만 하고 각 명령에 넘긴다
+    let workspace = app::Workspace::discover();
+    if let Ok(app::Workspace {
+        config: Ok(config), ..
+    }) = &workspace
+    {
+        i18n::apply_setting(&config.ui.locale);
+    }
+    complete::handle_env();
     silence_whisper_logs();
-    let cli = Cli::parse();
+    let cli = Cli::from_arg_matches(&cli::command().get_matches()).unwrap_or_else(|e| e.exit());
+
+    match cli.command {
+        Some(Commands::Completions { shell }) => {
+            if let Err(e) = complete::write_registration(shell, &mut std::io::stdout()) {
+                eprintln!("{}", tr!("cli.completions_failed", error = e));
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Commands::Man) => {
+            if let Err(e) = complete::write_man(&mut std::io::stdout()) {
+                eprintln!("{}", tr!("cli.man_failed", error = e));
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Commands::Config { action }) => {
+            run_config(workspace, action);
+            return;
+        }
+        Some(Commands::Global { action }) => {
+            run_global(workspace, action);
+            return;
+        }
+        Some(Commands::Prompt { format }) => {
+            // 실패하면 프롬프트를 어지럽히지 않도록 아무것도 출력하지 않는다
+            if let Ok(workspace) = &workspace
+                && let Some(segment) =
+                    prompt::segment(workspace, cli.branch.clone(), format.as_deref())
+            {
+                println!("{}", segment);
+            }
+            return;
+        }
+        _ => {}
+    }
 
-    let mut app_state = match AppState::init() {
+    let mut app_state = match workspace.and_then(AppState::open) {
         Ok(state) => state,
         Err(err) => {
-            eprintln!("초기화 실패: {}", err);
+            eprintln!("{}", tr!("cli.init_failed", error = err));
             return;
         }
     };
+    // TUI는 체크아웃된 브랜치를 따라가므로 --branch는 하위 명령에만 적용
+    if cli.command.is_some()
+        && let Some(branch) = cli.branch.clone()
+        && let Err(err) = app_state.select_branch(branch)
+    {
+        eprintln!("{}", tr!("cli.init_failed", error = err));
+        std::process::exit(1);
+    }
+    let branch = app_state.current_branch.clone();
+    if app_state.scratch
+        && matches!(
+            cli.command,
+            Some(Commands::Add { .. } | Commands::Voice { .. })
+        )
+    {
+        eprintln!("{}", tr!("cli.scratch_notice", name = branch));
+    }
+    // 전역 목록에는 노트를 쓰는 경로에서만 등록한다 (자동완성이나 조회 명령은 건너뜀)
+    if matches!(
+        cli.command,
+        None | Some(Commands::Add { .. } | Commands::Voice { .. })
+    ) {
+        app_state.register_globally();
+    }
 
     match cli.command {
         Some(Commands::Add { text }) => {
             let item = app_state
                 .log_store
-                .append_text(&app_state.current_branch, &text)
+                .append_text(&branch, &text)
                 .unwrap_or_else(|e| {
-                    eprintln!("로그 추가 실패: {}", e);
+                    eprintln!("{}", tr!("cli.add_failed", error = e));
                     std::process::exit(1);
                 });
 
             println!(
-                "✅ 로그 추가됨 [{}] {}",
-                item.created_at.format("%Y-%m-%d %H:%M:%S"),
-                item.text
+                "{}",
+                tr!(
+                    "cli.added",
+                    time = item.created_at.format("%Y-%m-%d %H:%M:%S"),
+                    text = item.text
+                )
             );
         }
 
         Some(Commands::List) => {
-            let items = app_state
-                .log_store
-                .list(&app_state.current_branch)
-                .unwrap_or_else(|e| {
-                    eprintln!("로그 조회 실패: {}", e);
-                    std::process::exit(1);
-                });
+            let items = app_state.log_store.list(&branch).unwrap_or_else(|e| {
+                eprintln!("{}", tr!("cli.list_failed", error = e));
+                std::process::exit(1);
+            });
 
             if items.is_empty() {
-                println!("📭 현재 브랜치에 로그가 없습니다");
+                println!("{}", tr!("cli.list_empty"));
                 return;
             }
 
             for item in items {
                 println!(
-                    "[{}] {}",
+                    "[{}] {} {}",
                     item.created_at.format("%Y-%m-%d %H:%M:%S"),
+                    item.id,
                     item.text
                 );
+                if let Some(snippet) = &item.snippet {
+                    println!("```\n{}\n```", snippet);
+                }
+                if item.cast.is_some() {
+                    println!("{}", tr!("cli.cast_hint", id = item.id));
+                }
             }
         }
 
-        Some(Commands::Voice { seconds }) => {
-            let model = voice::model::prepare_model_path_with_status(|msg| {
-                println!("{}", msg);
-            })
-            .unwrap_or_else(|e| {
-                eprintln!("모델 준비 실패: {}", e);
+        Some(Commands::Search { query, limit }) => {
+            let hits = app_state
+                .log_store
+                .search(&branch, &query)
+                .unwrap_or_else(|e| {
+                    eprintln!("{}", tr!("cli.list_failed", error = e));
+                    std::process::exit(1);
+                });
+
+            if hits.is_empty() {
+                println!("{}", tr!("cli.search_empty", query = query));
+                return;
+            }
+
+            let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+            for hit in hits.iter().take(limit.unwrap_or(usize::MAX)) {
+                let text = if color {
+                    highlight_ansi(&hit.item.text, &hit.highlights)
+                } else {
+                    hit.item.text.clone()
+                };
+                println!(
+                    "[{}] {} {}",
+                    hit.item.created_at.format("%Y-%m-%d %H:%M:%S"),
+                    hit.item.id,
+                    text
+                );
+            }
+        }
+
+        Some(Commands::Edit { id, text }) => {
+            let updated = app_state
+                .log_store
+                .update_text_by_id(&branch, &id, &text)
+                .unwrap_or_else(|e| {
+                    eprintln!("{}", tr!("cli.edit_failed", error = e));
+                    std::process::exit(1);
+                });
+
+            if !updated {
+                eprintln!("{}", tr!("cli.not_found", id = id));
+                std::process::exit(1);
+            }
+            println!("{}", tr!("cli.edited", id = id));
+        }
+
+        Some(Commands::Rm { id }) => {
+            let deleted = app_state
+                .log_store
+                .delete_by_id(&branch, &id)
+                .unwrap_or_else(|e| {
+                    eprintln!("{}", tr!("cli.rm_failed", error = e));
+                    std::process::exit(1);
+                });
+
+            if !deleted {
+                eprintln!("{}", tr!("cli.not_found", id = id));
+                std::process::exit(1);
+            }
+            println!("{}", tr!("cli.removed", id = id));
+        }
+
+        Some(Commands::Replay {
+            id,
+            speed,
+            idle_limit,
+        }) => {
+            let items = app_state.log_store.list(&branch).unwrap_or_else(|e| {
+                eprintln!("{}", tr!("cli.list_failed", error = e));
+                std::process::exit(1);
+            });
+            let Some(item) = items.iter().find(|item| item.id == id) else {
+                eprintln!("{}", tr!("cli.not_found", id = id));
+                std::process::exit(1);
+            };
+            let Some(cast) = &item.cast else {
+                eprintln!("{}", tr!("cli.no_cast", id = id));
+                std::process::exit(1);
+            };
+            let (header, events) = ui::cast::load(&app_state.log_store.cast_path(cast))
+                .unwrap_or_else(|e| {
+                    eprintln!("{}", tr!("cli.replay_failed", error = e));
+                    std::process::exit(1);
+                });
+            // 녹화한 화면보다 작으면 줄이 어긋나므로 미리 알려 준다
+            if std::io::stdout().is_terminal()
+                && let Ok((cols, rows)) = crossterm::terminal::size()
+                && (cols < header.width || rows < header.height)
+            {
+                eprintln!(
+                    "{}",
+                    tr!(
+                        "cli.replay_small_terminal",
+                        size = format!("{}x{}", header.width, header.height),
+                        current = format!("{}x{}", cols, rows)
+                    )
+                );
+            }
+            if let Err(e) = ui::cast::play(&events, speed, idle_limit, &mut std::io::stdout()) {
+                eprintln!("{}", tr!("cli.replay_failed", error = e));
+                std::process::exit(1);
+            }
+        }
+
+        Some(Commands::Adopt { from }) => {
+            if !app_state.in_repo {
+                eprintln!("{}", tr!("cli.adopt_not_repo"));
+                std::process::exit(1);
+            }
+            if app_state.scratch {
+                eprintln!("{}", tr!("cli.adopt_no_branch"));
+                std::process::exit(1);
+            }
+
+            let name = match from {
+                Some(from) if Path::new(&from).is_dir() => {
+                    app::scratch_name(&app_state.config, Path::new(&from))
+                }
+                Some(from) => from,
+                None => app_state.scratch_name(),
+            };
+            let scratch = app_state.scratch_store().unwrap_or_else(|e| {
+                eprintln!("{}", tr!("cli.init_failed", error = e));
                 std::process::exit(1);
             });
+            let items = scratch.list(&name).unwrap_or_else(|e| {
+                eprintln!("{}", tr!("cli.list_failed", error = e));
+                std::process::exit(1);
+            });
+            if items.is_empty() {
+                println!("{}", tr!("cli.adopt_empty", name = name));
+                return;
+            }
+
+            // 대상에 먼저 기록한 뒤 원본을 지워서 실패해도 노트가 사라지지 않게 한다
+            let casts = items
+                .iter()
+                .filter_map(|item| item.cast.as_deref())
+                .filter(|cast| scratch.cast_path(cast).exists())
+                .collect::<Vec<_>>();
+            for cast in &casts {
+                if let Err(e) = app_state.log_store.copy_cast(&scratch.cast_path(cast)) {
+                    eprintln!("{}", tr!("cli.add_failed", error = e));
+                    std::process::exit(1);
+                }
+            }
+            if let Err(e) = app_state.log_store.append_items(&branch, &items) {
+                eprintln!("{}", tr!("cli.add_failed", error = e));
+                std::process::exit(1);
+            }
+            if let Err(e) = scratch.remove_branch(&name) {
+                eprintln!("{}", tr!("cli.rm_failed", error = e));
+                std::process::exit(1);
+            }
+            for cast in casts {
+                if scratch.cast_path(cast) != app_state.log_store.cast_path(cast) {
+                    scratch.remove_cast(cast);
+                }
+            }
+            println!(
+                "{}",
+                tr!(
+                    "cli.adopted",
+                    count = items.len(),
+                    name = name,
+                    branch = branch
+                )
+            );
+        }
+
+        Some(Commands::Gc { dry_run, purge }) => run_gc(&app_state, dry_run, purge),
+
+        Some(Commands::Voice { seconds }) => {
+            let voice_config = &app_state.config.voice;
+            let model =
+                voice::model::prepare_model_path_with_status(&voice_config.model_url, |msg| {
+                    println!("{}", msg);
+                })
+                .unwrap_or_else(|e| {
+                    eprintln!("{}", tr!("voice.model_failed", error = e));
+                    std::process::exit(1);
+                });
 
-            let mut config = voice::VadConfig::default();
-            config.max_record_ms = (seconds.max(1) as u32) * 1000;
-            let text = voice::transcribe_from_mic_vad(&model.path, config)
+            let config = voice::VadConfig {
+                max_record_ms: (seconds.max(1) as u32) * 1000,
+                ..voice_config.vad
+            };
+            let text = voice::transcribe_from_mic_vad(&model.path, &voice_config.language, config)
                 .unwrap_or_else(|e| {
-                    eprintln!("보이스 인식 실패: {}", e);
+                    eprintln!("{}", tr!("voice.failed", error = e));
                     std::process::exit(1);
                 });
 
             let trimmed = text.trim();
             if trimmed.is_empty() {
-                println!("인식된 텍스트가 없습니다");
+                println!("{}", tr!("cli.voice_empty"));
                 return;
             }
 
             let item = app_state
                 .log_store
-                .append_text(&app_state.current_branch, trimmed)
+                .append_text(&branch, trimmed)
                 .unwrap_or_else(|e| {
-                    eprintln!("로그 추가 실패: {}", e);
+                    eprintln!("{}", tr!("cli.add_failed", error = e));
                     std::process::exit(1);
                 });
 
             println!(
-                "✅ 보이스 로그 추가됨 [{}] {}",
-                item.created_at.format("%Y-%m-%d %H:%M:%S"),
-                item.text
+                "{}",
+                tr!(
+                    "cli.voice_added",
+                    time = item.created_at.format("%Y-%m-%d %H:%M:%S"),
+                    text = item.text
+                )
             );
         }
 
+        // AppState 없이 위에서 처리됨
+        Some(
+            Commands::Completions { .. }
+            | Commands::Man
+            | Commands::Config { .. }
+            | Commands::Global { .. }
+            | Commands::Prompt { .. },
+        ) => {}
+
         None => {
             if let Err(e) = ui::tui::run(&mut app_state) {
-                eprintln!("TUI 실행 오류: {}", e);
+                eprintln!("{}", tr!("cli.tui_failed", error = e));
+            }
+        }
+    }
+}
+
+/// 검색어와 맞는 부분을 굵은 노란색으로 표시
+fn highlight_ansi(text: &str, ranges: &[Range<usize>]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for range in ranges {
+        out.push_str(&text[last..range.start]);
+        out.push_str("\x1b[1;33m");
+        out.push_str(&text[range.clone()]);
+        out.push_str("\x1b[0m");
+        last = range.end;
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+/// 브랜치가 없어진 repo 로그북을 보관하거나 지운다.
+fn run_gc(app_state: &AppState, dry_run: bool, purge: bool) {
+    if app_state.git.is_none() {
+        eprintln!("{}", tr!("cli.gc_not_repo"));
+        std::process::exit(1);
+    }
+    let (store, orphans) = app_state
+        .repo_store()
+        .and_then(|store| Ok((store, app_state.orphaned_logbooks()?)))
+        .unwrap_or_else(|e| {
+            eprintln!("{}", tr!("cli.gc_failed", error = e));
+            std::process::exit(1);
+        });
+    if orphans.is_empty() {
+        println!("{}", tr!("cli.gc_empty"));
+        return;
+    }
+
+    let now = Local::now();
+    for branch in &orphans {
+        let count = store.list(branch).map(|items| items.len()).unwrap_or(0);
+        if dry_run {
+            println!("{}", tr!("cli.gc_would", branch = branch, count = count));
+            continue;
+        }
+        let result = if purge {
+            // 보관할 때는 녹화도 남겨 두지만 완전 삭제면 같이 지운다
+            for item in store.list(branch).unwrap_or_default() {
+                if let Some(cast) = &item.cast {
+                    store.remove_cast(cast);
+                }
+            }
+            store
+                .remove_branch(branch)
+                .map(|_| tr!("cli.gc_purged", branch = branch, count = count))
+        } else {
+            log::archive::archive(&store, branch, now).map(|path| {
+                tr!(
+                    "cli.gc_archived",
+                    branch = branch,
+                    count = count,
+                    path = path.display()
+                )
+            })
+        };
+        match result {
+            Ok(message) => println!("{}", message),
+            Err(e) => {
+                eprintln!("{}", tr!("cli.gc_failed", error = e));
+                std::process::exit(1);
+            }
+        }
+    }
+    if dry_run {
+        println!("{}", tr!("cli.gc_dry_run_hint"));
+    }
+}
+
+/// 설정 파일 손상 시에도 고칠 수 있도록 AppState 없이 레이어만 읽는다.
+fn run_config(workspace: Result<app::Workspace, String>, action: ConfigAction) {
+    let repo_root = workspace
+        .map(|workspace| workspace.root)
+        .unwrap_or_else(|e| {
+            eprintln!("{}", tr!("cli.init_failed", error = e));
+            std::process::exit(1);
+        });
+    let mut layers = ConfigLayers::load(&repo_root).unwrap_or_else(|e| {
+        eprintln!("{}", tr!("cli.config_read_failed", error = e));
+        std::process::exit(1);
+    });
+
+    match action {
+        ConfigAction::Get { key } => match layers.get(&key) {
+            Ok(entry) => println!("{}", config::display_value(&entry.value)),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        },
+        ConfigAction::Set { key, value, global } => {
+            let scope = if global {
+                ConfigScope::User
+            } else {
+                ConfigScope::Repo
+            };
+            match layers.set(scope, &key, &value) {
+                Ok(path) => println!(
+                    "{}",
+                    tr!(
+                        "cli.config_saved",
+                        key = key,
+                        value = value,
+                        path = path.display()
+                    )
+                ),
+                Err(e) => {
+                    eprintln!("{}", tr!("cli.config_save_failed", error = e));
+                    std::process::exit(1);
+                }
+            }
+        }
+        ConfigAction::List => {
+            let entries = layers.entries().unwrap_or_else(|e| {
+                eprintln!("{}", tr!("cli.config_read_failed", error = e));
+                std::process::exit(1);
+            });
+            for entry in entries {
+                println!(
+                    "{} = {}  ({})",
+                    entry.key,
+                    config::display_value(&entry.value),
+                    entry.source.label()
+                );
+            }
+        }
+    }
+}
+
+/// 현재 repo 없이도 동작하도록 전역 목록만 읽는다.
+fn run_global(workspace: Result<app::Workspace, String>, action: GlobalAction) {
+    let mut registry = config::data_dir()
+        .and_then(|dir| Registry::load(&dir))
+        .unwrap_or_else(|e| {
+            eprintln!("{}", tr!("cli.global_failed", error = e));
+            std::process::exit(1);
+        });
+    let stale = registry.prune().unwrap_or_else(|e| {
+        eprintln!("{}", tr!("cli.global_failed", error = e));
+        std::process::exit(1);
+    });
+    for repo in stale {
+        eprintln!("{}", tr!("cli.global_pruned", path = repo.root.display()));
+    }
+
+    if registry.repos().is_empty() {
+        let enabled = workspace
+            .and_then(|workspace| workspace.config)
+            .is_ok_and(|config| config.global.enabled);
+        if !enabled {
+            println!("{}", tr!("cli.global_disabled"));
+        } else {
+            println!("{}", tr!("cli.global_empty"));
+        }
+        return;
+    }
+
+    let (items, limit) = match action {
+        GlobalAction::Repos => {
+            for repo in registry.repos() {
+                println!("{}  ({})", repo.root.display(), repo.log_dir.display());
+            }
+            return;
+        }
+        GlobalAction::List { limit } => (registry.collect(), limit),
+        GlobalAction::Search { query } => {
+            // `search`와 같은 순위/강조로 repo마다 찾아 점수순으로 합친다
+            let (hits, failed) = registry.search(&query);
+            report_skipped_repos(failed);
+            if hits.is_empty() {
+                println!("{}", tr!("cli.search_empty", query = query));
+                return;
+            }
+            let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+            for entry in &hits {
+                let text = if color {
+                    highlight_ansi(&entry.hit.item.text, &entry.hit.highlights)
+                } else {
+                    entry.hit.item.text.clone()
+                };
+                println!(
+                    "[{}] {}:{} {}",
+                    entry.hit.item.created_at.format("%Y-%m-%d %H:%M:%S"),
+                    repo_label(&entry.repo),
+                    entry.branch,
+                    text
+                );
             }
+            return;
         }
+    };
+    let (items, failed) = items;
+    report_skipped_repos(failed);
+
+    if items.is_empty() {
+        println!("{}", tr!("cli.global_empty"));
+        return;
+    }
+    let skip = limit.map_or(0, |n| items.len().saturating_sub(n));
+    for entry in &items[skip..] {
+        println!(
+            "[{}] {}:{} {}",
+            entry.item.created_at.format("%Y-%m-%d %H:%M:%S"),
+            repo_label(&entry.repo),
+            entry.branch,
+            entry.item.text
+        );
     }
 }
+
+/// 옮겨졌거나 로그북이 깨진 repo 하나 때문에 전체 조회를 멈추지 않는다
+fn report_skipped_repos(failed: Vec<(PathBuf, String)>) {
+    for (repo, error) in failed {
+        eprintln!(
+            "{}",
+            tr!("cli.global_skipped", path = repo.display(), error = error)
+        );
+    }
+}
+
+/// 전역 목록에 보여 줄 repo 이름 (디렉터리 이름, 없으면 전체 경로)
+fn repo_label(repo: &Path) -> String {
+    repo.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| repo.display().to_string())
+}
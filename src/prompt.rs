@@ -0,0 +1,58 @@
+//! 쉘 프롬프트용 요약. 프롬프트마다 실행되므로 AppState 없이 요약 파일만 읽는다.
+
+use chrono::Local;
+
+use crate::app::Workspace;
+use crate::i18n;
+use crate::log::store::LogStore;
+
+/// 프롬프트에 넣을 문자열. 로그가 없거나 읽지 못하면 None
+pub fn segment(
+    workspace: &Workspace,
+    branch: Option<String>,
+    format: Option<&str>,
+) -> Option<String> {
+    let git = workspace.git.as_ref()?;
+    let config = workspace.config.as_ref().ok()?;
+    let branch = branch.or_else(|| git.current_branch().ok())?;
+    // 로그북이 없는 repo에 디렉터리를 만들지 않는다
+    let log_dir = config.log_dir(&workspace.root);
+    if !log_dir.is_dir() {
+        return None;
+    }
+    let summary = LogStore::new(&log_dir)
+        .and_then(|store| store.summary(&branch))
+        .ok()?;
+    if summary.count == 0 {
+        return None;
+    }
+
+    let age = summary
+        .last_note_at
+        .map(|at| compact_age(Local::now() - at))
+        .unwrap_or_default();
+    Some(match format {
+        Some(format) => i18n::format(
+            format,
+            &[
+                ("branch", &branch),
+                ("count", &summary.count),
+                ("todos", &summary.open_todos),
+                ("age", &age),
+            ],
+        ),
+        None if summary.open_todos > 0 => format!("☐{} ✎{}", summary.open_todos, age),
+        None => format!("✎{}", age),
+    })
+}
+
+/// 프롬프트에 들어갈 짧은 경과 시간 (언어와 무관하게 5m, 4h, 3d)
+fn compact_age(elapsed: chrono::Duration) -> String {
+    let minutes = elapsed.num_minutes().max(0);
+    match minutes {
+        0 => "now".to_string(),
+        1..60 => format!("{}m", minutes),
+        60..1440 => format!("{}h", minutes / 60),
+        _ => format!("{}d", minutes / 1440),
+    }
+}
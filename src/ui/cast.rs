@@ -0,0 +1,348 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+
+/// Attributes, cursor visibility, alternate screen, mouse reporting, cursor/keypad
+/// application modes and bracketed paste back to their defaults.
+const RESET: &[u8] = b"\x1b[0m\x1b[?25h\
+    \x1b[?1000l\x1b[?1002l\x1b[?1003l\x1b[?1006l\x1b[?1l\x1b>\x1b[?2004l\r\n";
+
+/// First line of an asciicast v2 file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CastHeader {
+    pub version: u8,
+    pub width: u16,
+    pub height: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+}
+
+impl CastHeader {
+    pub fn new(rows: u16, cols: u16) -> Self {
+        let env = ["SHELL", "TERM"]
+            .into_iter()
+            .filter_map(|name| Some((name.to_string(), std::env::var(name).ok()?)))
+            .collect();
+        Self {
+            version: 2,
+            width: cols,
+            height: rows,
+            timestamp: Some(chrono::Local::now().timestamp()),
+            env,
+        }
+    }
+}
+
+/// One `[time, code, data]` line: `o` is output, `r` a resize to `COLSxROWS`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastEvent {
+    pub time: f64,
+    pub code: String,
+    pub data: String,
+}
+
+/// Writes asciicast v2 events. Output may arrive split inside a UTF-8 sequence, so an
+/// incomplete tail is held back until the next chunk.
+pub struct CastWriter<W: Write> {
+    out: W,
+    started: Instant,
+    pending: Vec<u8>,
+}
+
+impl<W: Write> CastWriter<W> {
+    pub fn new(mut out: W, header: &CastHeader) -> io::Result<Self> {
+        serde_json::to_writer(&mut out, header)?;
+        out.write_all(b"\n")?;
+        Ok(Self {
+            out,
+            started: Instant::now(),
+            pending: Vec::new(),
+        })
+    }
+
+    pub fn output(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.output_at(self.started.elapsed().as_secs_f64(), bytes)
+    }
+
+    pub fn resize(&mut self, rows: u16, cols: u16) -> io::Result<()> {
+        let time = self.started.elapsed().as_secs_f64();
+        self.event(time, "r", &format!("{cols}x{rows}"))
+    }
+
+    /// Writes what is still held back and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if !self.pending.is_empty() {
+            let time = self.started.elapsed().as_secs_f64();
+            let text = String::from_utf8_lossy(&self.pending).into_owned();
+            self.event(time, "o", &text)?;
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn output_at(&mut self, time: f64, bytes: &[u8]) -> io::Result<()> {
+        self.pending.extend_from_slice(bytes);
+        let keep = incomplete_tail(&self.pending);
+        let tail = self.pending.split_off(self.pending.len() - keep);
+        let chunk = std::mem::replace(&mut self.pending, tail);
+        if chunk.is_empty() {
+            return Ok(());
+        }
+        self.event(time, "o", &String::from_utf8_lossy(&chunk))
+    }
+
+    fn event(&mut self, time: f64, code: &str, data: &str) -> io::Result<()> {
+        // Microseconds are plenty and keep the lines short.
+        let time = (time * 1e6).round() / 1e6;
+        serde_json::to_writer(&mut self.out, &(time, code, data))?;
+        self.out.write_all(b"\n")
+    }
+}
+
+/// Length of a UTF-8 sequence cut off at the end of `bytes`, or 0.
+fn incomplete_tail(bytes: &[u8]) -> usize {
+    for back in 1..=bytes.len().min(3) {
+        let byte = bytes[bytes.len() - back];
+        if byte & 0xC0 == 0x80 {
+            continue;
+        }
+        let needed = match byte {
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => 1,
+        };
+        return if needed > back { back } else { 0 };
+    }
+    0
+}
+
+/// A recording of one terminal tab in progress. Write errors stop the recording and are
+/// reported by [`CastRecording::finish`].
+pub struct CastRecording {
+    writer: Option<CastWriter<BufWriter<File>>>,
+    path: PathBuf,
+    error: Option<io::Error>,
+}
+
+impl CastRecording {
+    /// Starts the file with `screen` (the panel as it looks now) so playback does not begin
+    /// on a blank terminal.
+    pub fn create(path: &Path, rows: u16, cols: u16, screen: &[u8]) -> Result<Self, String> {
+        let write_failed =
+            |e: io::Error| tr!("cast.write_failed", error = e, path = path.display());
+        let file = File::create(path).map_err(write_failed)?;
+        let mut writer = CastWriter::new(BufWriter::new(file), &CastHeader::new(rows, cols))
+            .map_err(write_failed)?;
+        writer.output_at(0.0, screen).map_err(write_failed)?;
+        Ok(Self {
+            writer: Some(writer),
+            path: path.to_path_buf(),
+            error: None,
+        })
+    }
+
+    pub fn output(&mut self, bytes: &[u8]) {
+        let result = self.writer.as_mut().map(|writer| writer.output(bytes));
+        self.keep_error(result);
+    }
+
+    pub fn resize(&mut self, rows: u16, cols: u16) {
+        let result = self.writer.as_mut().map(|writer| writer.resize(rows, cols));
+        self.keep_error(result);
+    }
+
+    /// Closes the file and returns its path.
+    pub fn finish(mut self) -> Result<PathBuf, String> {
+        let result = match self.writer.take() {
+            Some(writer) => writer.finish().map(|_| ()),
+            None => Ok(()),
+        };
+        match self.error.take().map_or(result, Err) {
+            Ok(()) => Ok(self.path),
+            Err(e) => Err(tr!(
+                "cast.write_failed",
+                error = e,
+                path = self.path.display()
+            )),
+        }
+    }
+
+    fn keep_error(&mut self, result: Option<io::Result<()>>) {
+        if let Some(Err(e)) = result {
+            self.writer = None;
+            self.error = Some(e);
+        }
+    }
+}
+
+/// Reads an asciicast v2 file. Event codes other than output and resize are kept as is.
+pub fn parse(text: &str) -> Result<(CastHeader, Vec<CastEvent>), String> {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let header = lines
+        .next()
+        .ok_or_else(|| tr!("cast.empty"))
+        .and_then(|line| {
+            serde_json::from_str::<CastHeader>(line)
+                .map_err(|e| tr!("cast.parse_failed", line = 1, error = e))
+        })?;
+    if header.version != 2 {
+        return Err(tr!("cast.unsupported_version", version = header.version));
+    }
+    let events = lines
+        .enumerate()
+        .map(|(index, line)| {
+            serde_json::from_str::<(f64, String, String)>(line)
+                .map(|(time, code, data)| CastEvent { time, code, data })
+                .map_err(|e| tr!("cast.parse_failed", line = index + 2, error = e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((header, events))
+}
+
+/// Reads a recording from disk.
+pub fn load(path: &Path) -> Result<(CastHeader, Vec<CastEvent>), String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| tr!("cast.read_failed", error = e, path = path.display()))?;
+    parse(&text)
+}
+
+/// Plays the output events to `out` in real time, `speed` times faster, with pauses cut to
+/// `idle_limit` seconds.
+pub fn play(
+    events: &[CastEvent],
+    speed: f64,
+    idle_limit: Option<f64>,
+    out: &mut impl Write,
+) -> Result<(), String> {
+    let speed = if speed > 0.0 { speed } else { 1.0 };
+    let write_failed = |e: io::Error| tr!("cast.play_failed", error = e);
+
+    // Only to know which screen the recording ends on.
+    let mut parser = vt100::Parser::new(24, 80, 0);
+    let mut last = 0.0;
+    for event in events.iter().filter(|event| event.code == "o") {
+        let mut delay = event.time - last;
+        if let Some(limit) = idle_limit {
+            delay = delay.min(limit);
+        }
+        last = event.time;
+        // Out-of-order or absurd timestamps must not panic; they just play without a pause.
+        let pause = Duration::try_from_secs_f64((delay / speed).max(0.0)).unwrap_or_default();
+        thread::sleep(pause);
+        out.write_all(event.data.as_bytes()).map_err(write_failed)?;
+        out.flush().map_err(write_failed)?;
+        parser.process(event.data.as_bytes());
+    }
+    // The recording may stop inside a full-screen program: leave the alternate screen and
+    // turn off the modes it set so the terminal is usable again.
+    if parser.screen().alternate_screen() {
+        out.write_all(b"\x1b[?1049l").map_err(write_failed)?;
+    }
+    out.write_all(RESET)
+        .and_then(|_| out.flush())
+        .map_err(write_failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header() -> CastHeader {
+        CastHeader {
+            version: 2,
+            width: 80,
+            height: 24,
+            timestamp: None,
+            env: BTreeMap::new(),
+        }
+    }
+
+    fn event(time: f64, code: &str, data: &str) -> CastEvent {
+        CastEvent {
+            time,
+            code: code.to_string(),
+            data: data.to_string(),
+        }
+    }
+
+    #[test]
+    fn writes_header_and_events() {
+        let mut writer = CastWriter::new(Vec::new(), &header()).unwrap();
+        writer.output_at(0.25, b"$ ls\r\n").unwrap();
+        writer.output_at(1.0000004, b"\x1b[31mred\x1b[0m").unwrap();
+        let out = String::from_utf8(writer.finish().unwrap()).unwrap();
+        assert_eq!(
+            out,
+            "{\"version\":2,\"width\":80,\"height\":24}\n\
+             [0.25,\"o\",\"$ ls\\r\\n\"]\n\
+             [1.0,\"o\",\"\\u001b[31mred\\u001b[0m\"]\n"
+        );
+        let (parsed, events) = parse(&out).unwrap();
+        assert_eq!(parsed, header());
+        assert_eq!(events[1], event(1.0, "o", "\x1b[31mred\x1b[0m"));
+    }
+
+    #[test]
+    fn holds_back_split_utf8() {
+        let bytes = "빌드 실패".as_bytes();
+        let mut writer = CastWriter::new(Vec::new(), &header()).unwrap();
+        writer.output_at(0.1, &bytes[..4]).unwrap();
+        writer.output_at(0.2, &bytes[4..5]).unwrap();
+        writer.output_at(0.3, &bytes[5..]).unwrap();
+        let out = String::from_utf8(writer.finish().unwrap()).unwrap();
+        let (_, events) = parse(&out).unwrap();
+        assert_eq!(events, [event(0.1, "o", "빌"), event(0.3, "o", "드 실패")]);
+
+        // Bytes that can never become valid text are replaced, not held forever.
+        let mut writer = CastWriter::new(Vec::new(), &header()).unwrap();
+        writer.output_at(0.1, b"a\xffb").unwrap();
+        writer.output_at(0.2, b"\xe2\x94").unwrap();
+        let out = String::from_utf8(writer.finish().unwrap()).unwrap();
+        let (_, events) = parse(&out).unwrap();
+        assert_eq!(events[0], event(0.1, "o", "a\u{fffd}b"));
+        assert_eq!(events[1].data, "\u{fffd}");
+    }
+
+    #[test]
+    fn parses_other_recorders() {
+        let text = "{\"version\": 2, \"width\": 100, \"height\": 30, \"timestamp\": 1504467315, \
+                    \"title\": \"demo\", \"env\": {\"TERM\": \"xterm-256color\"}}\n\
+                    [0.248848, \"o\", \"hi\"]\n\
+                    [1.001376, \"i\", \"q\"]\n\
+                    [2.5, \"r\", \"90x20\"]\n";
+        let (header, events) = parse(text).unwrap();
+        assert_eq!((header.width, header.height), (100, 30));
+        assert_eq!(header.env["TERM"], "xterm-256color");
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[2], event(2.5, "r", "90x20"));
+
+        assert!(parse("").is_err());
+        assert!(parse("{\"version\": 1, \"width\": 80, \"height\": 24}").is_err());
+        assert!(parse("{\"version\": 2, \"width\": 80, \"height\": 24}\n[0.1, \"o\"]").is_err());
+    }
+
+    #[test]
+    fn odd_timestamps_play_without_pausing() {
+        let events = [
+            event(1e30, "o", "a"),
+            event(0.5, "o", "b"),
+            event(f64::MAX, "o", "c"),
+        ];
+        let started = std::time::Instant::now();
+        let mut out = Vec::new();
+        play(&events, 1.0, Some(0.0), &mut out).unwrap();
+        play(&events, 1.0, None, &mut out).unwrap();
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(out.starts_with(b"abc"));
+    }
+}
@@ -0,0 +1,336 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use vt100::Screen;
+
+/// Terminal modes that change what a key sends, as set by the program in the PTY.
+///
+/// DECKPAM (application keypad) is not honoured: without keyboard enhancement, which we do
+/// not request, the outer terminal sends keypad keys exactly like the main keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeyModes {
+    /// DECCKM: unmodified cursor keys send `ESC O x` instead of `ESC [ x`.
+    pub application_cursor: bool,
+}
+
+impl KeyModes {
+    pub fn from_screen(screen: &Screen) -> Self {
+        Self {
+            application_cursor: screen.application_cursor(),
+        }
+    }
+}
+
+/// Converts crossterm key events into the byte sequences xterm sends for them.
+pub fn encode_key_event(key: KeyEvent, modes: KeyModes) -> Option<Vec<u8>> {
+    if !matches!(key.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+        return None;
+    }
+    let mods = key.modifiers;
+    let alt = mods.contains(KeyModifiers::ALT);
+
+    let bytes = match key.code {
+        KeyCode::Char(c) => {
+            let mut bytes = if mods.contains(KeyModifiers::CONTROL) {
+                vec![control_byte(c)?]
+            } else {
+                c.to_string().into_bytes()
+            };
+            if alt {
+                bytes.insert(0, 0x1b);
+            }
+            bytes
+        }
+        KeyCode::Enter => with_alt(b"\r", alt),
+        KeyCode::Tab => with_alt(b"\t", alt),
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Esc => with_alt(b"\x1b", alt),
+        KeyCode::Backspace if mods.contains(KeyModifiers::CONTROL) => with_alt(b"\x08", alt),
+        KeyCode::Backspace => with_alt(b"\x7f", alt),
+        KeyCode::Up => cursor(b'A', mods, modes),
+        KeyCode::Down => cursor(b'B', mods, modes),
+        KeyCode::Right => cursor(b'C', mods, modes),
+        KeyCode::Left => cursor(b'D', mods, modes),
+        KeyCode::Home => cursor(b'H', mods, modes),
+        KeyCode::End => cursor(b'F', mods, modes),
+        KeyCode::KeypadBegin => cursor(b'E', mods, modes),
+        KeyCode::Insert => tilde(2, modifier_param(mods)),
+        KeyCode::Delete => tilde(3, modifier_param(mods)),
+        KeyCode::PageUp => tilde(5, modifier_param(mods)),
+        KeyCode::PageDown => tilde(6, modifier_param(mods)),
+        KeyCode::F(n) => function_key(n, mods)?,
+        _ => return None,
+    };
+    Some(bytes)
+}
+
+/// Text for a paste. Programs that enabled bracketed paste get it wrapped in
+/// `ESC [ 200 ~` / `ESC [ 201 ~` so they can tell it from typing; others get it as if typed.
+pub fn encode_paste(text: &str, bracketed: bool) -> Vec<u8> {
+    // Line endings become Enter, like a terminal does.
+    let text = text.replace("\r\n", "\r").replace('\n', "\r");
+    if !bracketed {
+        return text.into_bytes();
+    }
+    // Like xterm, drop every ESC: an end marker could otherwise be smuggled in, e.g. one
+    // that only forms once an inner marker is removed.
+    let text = text.replace('\x1b', "");
+    let mut bytes = b"\x1b[200~".to_vec();
+    bytes.extend_from_slice(text.as_bytes());
+    bytes.extend_from_slice(b"\x1b[201~");
+    bytes
+}
+
+/// xterm modifier parameter: 1 + Shift(1) + Alt(2) + Ctrl(4); `None` when unmodified.
+fn modifier_param(mods: KeyModifiers) -> Option<u8> {
+    let mut bits = 0;
+    if mods.contains(KeyModifiers::SHIFT) {
+        bits |= 1;
+    }
+    if mods.contains(KeyModifiers::ALT) {
+        bits |= 2;
+    }
+    if mods.contains(KeyModifiers::CONTROL) {
+        bits |= 4;
+    }
+    (bits != 0).then_some(bits + 1)
+}
+
+fn control_byte(c: char) -> Option<u8> {
+    Some(match c.to_ascii_uppercase() {
+        '@' | ' ' | '2' => 0x00,
+        upper @ 'A'..='Z' => upper as u8 - b'A' + 1,
+        '[' | '3' => 0x1b,
+        '\\' | '4' => 0x1c,
+        ']' | '5' => 0x1d,
+        '^' | '6' | '~' => 0x1e,
+        '_' | '7' | '/' => 0x1f,
+        '?' | '8' => 0x7f,
+        _ => return None,
+    })
+}
+
+fn with_alt(bytes: &[u8], alt: bool) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len() + 1);
+    if alt {
+        out.push(0x1b);
+    }
+    out.extend_from_slice(bytes);
+    out
+}
+
+fn ss3(final_byte: u8) -> Vec<u8> {
+    vec![0x1b, b'O', final_byte]
+}
+
+/// Cursor keys: `ESC [ x` (or `ESC O x` in application mode), `ESC [ 1 ; m x` when modified.
+fn cursor(final_byte: u8, mods: KeyModifiers, modes: KeyModes) -> Vec<u8> {
+    match modifier_param(mods) {
+        Some(param) => format!("\x1b[1;{}{}", param, final_byte as char).into_bytes(),
+        None if modes.application_cursor => ss3(final_byte),
+        None => vec![0x1b, b'[', final_byte],
+    }
+}
+
+/// `ESC [ n ~`, or `ESC [ n ; m ~` when modified.
+fn tilde(code: u8, param: Option<u8>) -> Vec<u8> {
+    match param {
+        Some(param) => format!("\x1b[{};{}~", code, param).into_bytes(),
+        None => format!("\x1b[{}~", code).into_bytes(),
+    }
+}
+
+/// F1–F12 as xterm sends them. Higher keys follow xterm's terminfo, where every block of
+/// twelve is F1–F12 with a modifier: F13–F24 Shift, F25–F36 Ctrl, F37–F48 Ctrl+Shift,
+/// F49–F60 Alt, F61–F63 Alt+Shift.
+fn function_key(n: u8, mods: KeyModifiers) -> Option<Vec<u8>> {
+    if !(1..=63).contains(&n) {
+        return None;
+    }
+    let implied = match (n - 1) / 12 {
+        0 => KeyModifiers::NONE,
+        1 => KeyModifiers::SHIFT,
+        2 => KeyModifiers::CONTROL,
+        3 => KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        4 => KeyModifiers::ALT,
+        _ => KeyModifiers::ALT | KeyModifiers::SHIFT,
+    };
+    let param = modifier_param(mods | implied);
+    Some(match (n - 1) % 12 + 1 {
+        base @ 1..=4 => {
+            let final_byte = b"PQRS"[usize::from(base - 1)];
+            match param {
+                Some(param) => format!("\x1b[1;{}{}", param, final_byte as char).into_bytes(),
+                None => ss3(final_byte),
+            }
+        }
+        base => {
+            let code = [15, 17, 18, 19, 20, 21, 23, 24][usize::from(base - 5)];
+            tilde(code, param)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NONE: KeyModifiers = KeyModifiers::NONE;
+    const SHIFT: KeyModifiers = KeyModifiers::SHIFT;
+    const ALT: KeyModifiers = KeyModifiers::ALT;
+    const CTRL: KeyModifiers = KeyModifiers::CONTROL;
+
+    const NORMAL: KeyModes = KeyModes {
+        application_cursor: false,
+    };
+    const APP_CURSOR: KeyModes = KeyModes {
+        application_cursor: true,
+    };
+
+    fn check(cases: &[(KeyCode, KeyModifiers, KeyModes, &str)]) {
+        for &(code, mods, modes, expected) in cases {
+            let bytes = encode_key_event(KeyEvent::new(code, mods), modes);
+            assert_eq!(
+                bytes.as_deref(),
+                Some(expected.as_bytes()),
+                "{code:?} {mods:?} {modes:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn characters_and_control_keys() {
+        check(&[
+            (KeyCode::Char('a'), NONE, NORMAL, "a"),
+            (KeyCode::Char('A'), SHIFT, NORMAL, "A"),
+            (KeyCode::Char('한'), NONE, NORMAL, "한"),
+            (KeyCode::Char('c'), CTRL, NORMAL, "\x03"),
+            (KeyCode::Char('C'), CTRL | SHIFT, NORMAL, "\x03"),
+            (KeyCode::Char(' '), CTRL, NORMAL, "\x00"),
+            (KeyCode::Char('['), CTRL, NORMAL, "\x1b"),
+            (KeyCode::Char('\\'), CTRL, NORMAL, "\x1c"),
+            (KeyCode::Char(']'), CTRL, NORMAL, "\x1d"),
+            (KeyCode::Char('^'), CTRL, NORMAL, "\x1e"),
+            (KeyCode::Char('_'), CTRL, NORMAL, "\x1f"),
+            (KeyCode::Char('/'), CTRL, NORMAL, "\x1f"),
+            (KeyCode::Char('?'), CTRL, NORMAL, "\x7f"),
+            (KeyCode::Char('2'), CTRL, NORMAL, "\x00"),
+            (KeyCode::Char('x'), ALT, NORMAL, "\x1bx"),
+            (KeyCode::Char('x'), CTRL | ALT, NORMAL, "\x1b\x18"),
+            (KeyCode::Enter, NONE, NORMAL, "\r"),
+            (KeyCode::Enter, ALT, NORMAL, "\x1b\r"),
+            (KeyCode::Tab, NONE, NORMAL, "\t"),
+            (KeyCode::BackTab, SHIFT, NORMAL, "\x1b[Z"),
+            (KeyCode::Esc, NONE, NORMAL, "\x1b"),
+            (KeyCode::Backspace, NONE, NORMAL, "\x7f"),
+            (KeyCode::Backspace, CTRL, NORMAL, "\x08"),
+            (KeyCode::Backspace, ALT, NORMAL, "\x1b\x7f"),
+        ]);
+        assert_eq!(
+            encode_key_event(KeyEvent::new(KeyCode::Char('1'), CTRL), NORMAL),
+            None
+        );
+    }
+
+    #[test]
+    fn cursor_keys_follow_decckm_and_modifiers() {
+        check(&[
+            (KeyCode::Up, NONE, NORMAL, "\x1b[A"),
+            (KeyCode::Down, NONE, NORMAL, "\x1b[B"),
+            (KeyCode::Right, NONE, NORMAL, "\x1b[C"),
+            (KeyCode::Left, NONE, NORMAL, "\x1b[D"),
+            (KeyCode::Home, NONE, NORMAL, "\x1b[H"),
+            (KeyCode::End, NONE, NORMAL, "\x1b[F"),
+            (KeyCode::Up, NONE, APP_CURSOR, "\x1bOA"),
+            (KeyCode::Left, NONE, APP_CURSOR, "\x1bOD"),
+            (KeyCode::Home, NONE, APP_CURSOR, "\x1bOH"),
+            (KeyCode::End, NONE, APP_CURSOR, "\x1bOF"),
+            (KeyCode::Up, SHIFT, NORMAL, "\x1b[1;2A"),
+            (KeyCode::Down, ALT, NORMAL, "\x1b[1;3B"),
+            (KeyCode::Right, CTRL, NORMAL, "\x1b[1;5C"),
+            (KeyCode::Left, CTRL | SHIFT, NORMAL, "\x1b[1;6D"),
+            (KeyCode::Home, CTRL | ALT, NORMAL, "\x1b[1;7H"),
+            (KeyCode::End, CTRL | ALT | SHIFT, NORMAL, "\x1b[1;8F"),
+            // Modified cursor keys ignore application mode.
+            (KeyCode::Up, CTRL, APP_CURSOR, "\x1b[1;5A"),
+        ]);
+    }
+
+    #[test]
+    fn editing_keys() {
+        check(&[
+            (KeyCode::Insert, NONE, NORMAL, "\x1b[2~"),
+            (KeyCode::Delete, NONE, NORMAL, "\x1b[3~"),
+            (KeyCode::PageUp, NONE, NORMAL, "\x1b[5~"),
+            (KeyCode::PageDown, NONE, APP_CURSOR, "\x1b[6~"),
+            (KeyCode::Delete, CTRL, NORMAL, "\x1b[3;5~"),
+            (KeyCode::PageUp, SHIFT, NORMAL, "\x1b[5;2~"),
+            (KeyCode::PageDown, ALT, NORMAL, "\x1b[6;3~"),
+        ]);
+    }
+
+    #[test]
+    fn function_keys() {
+        check(&[
+            (KeyCode::F(1), NONE, NORMAL, "\x1bOP"),
+            (KeyCode::F(4), NONE, NORMAL, "\x1bOS"),
+            (KeyCode::F(5), NONE, NORMAL, "\x1b[15~"),
+            (KeyCode::F(6), NONE, NORMAL, "\x1b[17~"),
+            (KeyCode::F(10), NONE, NORMAL, "\x1b[21~"),
+            (KeyCode::F(11), NONE, NORMAL, "\x1b[23~"),
+            (KeyCode::F(12), NONE, NORMAL, "\x1b[24~"),
+            (KeyCode::F(1), SHIFT, NORMAL, "\x1b[1;2P"),
+            (KeyCode::F(2), CTRL, NORMAL, "\x1b[1;5Q"),
+            (KeyCode::F(5), CTRL, NORMAL, "\x1b[15;5~"),
+            (KeyCode::F(12), ALT | SHIFT, NORMAL, "\x1b[24;4~"),
+            (KeyCode::F(13), NONE, NORMAL, "\x1b[1;2P"),
+            (KeyCode::F(16), NONE, NORMAL, "\x1b[1;2S"),
+            (KeyCode::F(17), NONE, NORMAL, "\x1b[15;2~"),
+            (KeyCode::F(24), NONE, NORMAL, "\x1b[24;2~"),
+            (KeyCode::F(25), NONE, NORMAL, "\x1b[1;5P"),
+            (KeyCode::F(37), NONE, NORMAL, "\x1b[1;6P"),
+            (KeyCode::F(49), NONE, NORMAL, "\x1b[1;3P"),
+            (KeyCode::F(63), NONE, NORMAL, "\x1b[1;4R"),
+        ]);
+        assert_eq!(
+            encode_key_event(KeyEvent::new(KeyCode::F(64), NONE), NORMAL),
+            None
+        );
+    }
+
+    #[test]
+    fn keypad_center_is_a_cursor_key() {
+        check(&[
+            (KeyCode::KeypadBegin, NONE, NORMAL, "\x1b[E"),
+            (KeyCode::KeypadBegin, NONE, APP_CURSOR, "\x1bOE"),
+            (KeyCode::KeypadBegin, CTRL, APP_CURSOR, "\x1b[1;5E"),
+        ]);
+    }
+
+    #[test]
+    fn releases_are_ignored() {
+        let mut key = KeyEvent::new(KeyCode::Char('a'), NONE);
+        key.kind = KeyEventKind::Release;
+        assert_eq!(encode_key_event(key, NORMAL), None);
+        key.kind = KeyEventKind::Repeat;
+        assert_eq!(encode_key_event(key, NORMAL), Some(b"a".to_vec()));
+    }
+
+    #[test]
+    fn paste_encoding() {
+        assert_eq!(encode_paste("ls\necho hi\n", false), b"ls\recho hi\r");
+        assert_eq!(encode_paste("a\r\nb", false), b"a\rb");
+        assert_eq!(
+            encode_paste("make\ntest", true),
+            b"\x1b[200~make\rtest\x1b[201~"
+        );
+        assert_eq!(
+            encode_paste("evil\x1b[201~rm -rf ~\n", true),
+            b"\x1b[200~evil[201~rm -rf ~\r\x1b[201~"
+        );
+        assert_eq!(
+            encode_paste("x\x1b[20\x1b[201~1~y", true),
+            b"\x1b[200~x[20[201~1~y\x1b[201~"
+        );
+        // Typed-looking pastes keep their escapes.
+        assert_eq!(encode_paste("\x1b[A", false), b"\x1b[A");
+    }
+}
@@ -0,0 +1,266 @@
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::config::KeysConfig;
+use crate::i18n::tr;
+
+/// A single key binding parsed from config, e.g. `"i"`, `"Esc"`, `"F2"`, `"Ctrl+b"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            return Err(tr!("keys.empty"));
+        }
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = spec;
+        // A lone "+" is a key, not a separator.
+        while let Some((prefix, tail)) = rest.split_once('+').filter(|(_, tail)| !tail.is_empty()) {
+            modifiers |= match prefix.to_ascii_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(tr!("keys.unknown_modifier", modifier = prefix, spec = spec)),
+            };
+            rest = tail;
+        }
+
+        let code = match rest.to_ascii_lowercase().as_str() {
+            "esc" | "escape" => KeyCode::Esc,
+            "enter" | "return" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "insert" => KeyCode::Insert,
+            "delete" | "del" => KeyCode::Delete,
+            lower => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                        Some(n) if (1..=24).contains(&n) => KeyCode::F(n),
+                        _ => return Err(tr!("keys.unknown_key", key = rest, spec = spec)),
+                    },
+                }
+            }
+        };
+
+        Ok(Self { code, modifiers })
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        // Terminals report uppercase letters with SHIFT; the char itself already carries it.
+        let ignore = match key.code {
+            KeyCode::Char(_) => KeyModifiers::SHIFT,
+            _ => KeyModifiers::NONE,
+        };
+        let wanted = self.modifiers - ignore;
+        let got = key.modifiers - ignore;
+
+        match (self.code, key.code) {
+            (KeyCode::Char(a), KeyCode::Char(b)) if wanted.contains(KeyModifiers::CONTROL) => {
+                a.eq_ignore_ascii_case(&b) && got == wanted
+            }
+            (a, b) => a == b && got == wanted,
+        }
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// Resolved TUI key bindings.
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    pub toggle_focus: KeyBinding,
+    pub add: KeyBinding,
+    pub edit: KeyBinding,
+    pub delete: KeyBinding,
+    pub search: KeyBinding,
+    pub voice: KeyBinding,
+    pub quit: KeyBinding,
+    pub debug: KeyBinding,
+    pub log_command: KeyBinding,
+    pub select: KeyBinding,
+    pub new_tab: KeyBinding,
+    pub prev_tab: KeyBinding,
+    pub next_tab: KeyBinding,
+    pub close_tab: KeyBinding,
+    pub restart_shell: KeyBinding,
+    pub restart_shell_cwd: KeyBinding,
+    pub term_search: KeyBinding,
+    pub record: KeyBinding,
+    pub zoom: KeyBinding,
+    pub layout: KeyBinding,
+    pub grow: KeyBinding,
+    pub shrink: KeyBinding,
+    /// Prefix key for shortcuts while the terminal has focus (None = shortcuts work directly).
+    pub leader: Option<KeyBinding>,
+}
+
+/// Where a key pressed while the terminal has focus should go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    /// Forward to the shell.
+    Shell,
+    /// Check it against the TUI bindings (anything unbound still reaches the shell).
+    Tui,
+    /// The leader itself; swallow it and wait for the next key.
+    Leader,
+}
+
+impl KeyMap {
+    pub fn from_config(keys: &KeysConfig) -> Result<Self, String> {
+        let parse = |name: &str, spec: &str| {
+            KeyBinding::parse(spec).map_err(|e| tr!("keys.invalid", name = name, error = e))
+        };
+        Ok(Self {
+            toggle_focus: parse("toggle_focus", &keys.toggle_focus)?,
+            add: parse("add", &keys.add)?,
+            edit: parse("edit", &keys.edit)?,
+            delete: parse("delete", &keys.delete)?,
+            search: parse("search", &keys.search)?,
+            voice: parse("voice", &keys.voice)?,
+            quit: parse("quit", &keys.quit)?,
+            debug: parse("debug", &keys.debug)?,
+            log_command: parse("log_command", &keys.log_command)?,
+            select: parse("select", &keys.select)?,
+            new_tab: parse("new_tab", &keys.new_tab)?,
+            prev_tab: parse("prev_tab", &keys.prev_tab)?,
+            next_tab: parse("next_tab", &keys.next_tab)?,
+            close_tab: parse("close_tab", &keys.close_tab)?,
+            restart_shell: parse("restart_shell", &keys.restart_shell)?,
+            restart_shell_cwd: parse("restart_shell_cwd", &keys.restart_shell_cwd)?,
+            term_search: parse("term_search", &keys.term_search)?,
+            record: parse("record", &keys.record)?,
+            zoom: parse("zoom", &keys.zoom)?,
+            layout: parse("layout", &keys.layout)?,
+            grow: parse("grow", &keys.grow)?,
+            shrink: parse("shrink", &keys.shrink)?,
+            leader: match keys.leader.trim() {
+                "" => None,
+                spec => Some(parse("leader", spec)?),
+            },
+        })
+    }
+
+    /// How to press `binding` from the terminal, for hints: `"F10"` or `"Ctrl+b F10"`.
+    pub fn in_terminal(&self, binding: KeyBinding) -> String {
+        match self.leader {
+            Some(leader) => format!("{leader} {binding}"),
+            None => binding.to_string(),
+        }
+    }
+
+    /// Whether the layout keys (zoom, layout, grow, shrink) are taken from a live terminal.
+    /// Their defaults are Alt combinations shells use for line editing (`Alt+l` lowercases
+    /// a word), so without a leader the shell keeps them and they work from the log panel.
+    pub fn layout_keys_in_terminal(&self) -> bool {
+        self.leader.is_some()
+    }
+
+    /// Routes a key typed into the terminal. Without a leader every key is a
+    /// candidate shortcut; with one, only the key right after it is, so `Esc`
+    /// and friends reach programs like vim. Pressing the leader twice sends it
+    /// to the shell. `pending` carries the leader press between calls.
+    pub fn route(&self, key: &KeyEvent, pending: &mut bool) -> Route {
+        let Some(leader) = self.leader else {
+            return Route::Tui;
+        };
+        if std::mem::take(pending) {
+            return if leader.matches(key) {
+                Route::Shell
+            } else {
+                Route::Tui
+            };
+        }
+        if leader.matches(key) {
+            *pending = true;
+            return Route::Leader;
+        }
+        Route::Shell
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn parses_and_matches_bindings() {
+        let ctrl_b = KeyBinding::parse("Ctrl+b").unwrap();
+        assert!(ctrl_b.matches(&key(KeyCode::Char('b'), KeyModifiers::CONTROL)));
+        assert!(!ctrl_b.matches(&key(KeyCode::Char('b'), KeyModifiers::NONE)));
+        assert_eq!(ctrl_b.to_string(), "Ctrl+b");
+        assert_eq!(KeyBinding::parse("Alt++").unwrap().to_string(), "Alt++");
+        assert!(KeyBinding::parse("Hyper+x").is_err());
+        assert!(KeyBinding::parse("F25").is_err());
+    }
+
+    #[test]
+    fn leader_gates_shortcuts_in_the_terminal() {
+        let esc = key(KeyCode::Esc, KeyModifiers::NONE);
+        let ctrl_b = key(KeyCode::Char('b'), KeyModifiers::CONTROL);
+        let mut pending = false;
+
+        let plain = KeyMap::from_config(&KeysConfig::default()).unwrap();
+        assert_eq!(plain.route(&esc, &mut pending), Route::Tui);
+
+        let keys = KeyMap::from_config(&KeysConfig {
+            leader: "Ctrl+b".to_string(),
+            ..KeysConfig::default()
+        })
+        .unwrap();
+        assert_eq!(keys.route(&esc, &mut pending), Route::Shell);
+        assert_eq!(keys.route(&ctrl_b, &mut pending), Route::Leader);
+        assert!(pending);
+        assert_eq!(keys.route(&esc, &mut pending), Route::Tui);
+        assert!(!pending);
+        keys.route(&ctrl_b, &mut pending);
+        assert_eq!(keys.route(&ctrl_b, &mut pending), Route::Shell);
+        assert!(!pending);
+        // Alt+l and friends stay with the shell's line editor unless a leader is set.
+        assert!(!plain.layout_keys_in_terminal());
+        assert!(keys.layout_keys_in_terminal());
+    }
+}
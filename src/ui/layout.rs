@@ -0,0 +1,262 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+use crate::config::UiConfig;
+use crate::i18n::tr;
+
+/// 터미널 패널이 차지하는 비율(%)의 범위와 한 번에 바뀌는 양
+const SPLIT_MIN: u16 = 20;
+const SPLIT_MAX: u16 = 80;
+const SPLIT_STEP: u16 = 5;
+
+/// 본문(터미널 + 로그 목록) 배치
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// 좌우로 나란히 (왼쪽 터미널)
+    Side,
+    /// 위아래로 (위 터미널)
+    Stacked,
+    /// 터미널만
+    Terminal,
+    /// 로그 목록만
+    Logs,
+}
+
+impl Preset {
+    /// `ui.layout` 값
+    pub fn from_setting(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "side" => Some(Self::Side),
+            "stacked" => Some(Self::Stacked),
+            "terminal" => Some(Self::Terminal),
+            "logs" => Some(Self::Logs),
+            _ => None,
+        }
+    }
+
+    pub fn as_setting(self) -> &'static str {
+        match self {
+            Self::Side => "side",
+            Self::Stacked => "stacked",
+            Self::Terminal => "terminal",
+            Self::Logs => "logs",
+        }
+    }
+
+    /// 상태 표시줄에 보여 줄 이름
+    pub fn label(self) -> String {
+        match self {
+            Self::Side => tr!("tui.layout.side"),
+            Self::Stacked => tr!("tui.layout.stacked"),
+            Self::Terminal => tr!("tui.layout.terminal"),
+            Self::Logs => tr!("tui.layout.logs"),
+        }
+    }
+
+    /// 배치 전환 키를 누를 때의 순서
+    pub fn next(self) -> Self {
+        match self {
+            Self::Side => Self::Stacked,
+            Self::Stacked => Self::Terminal,
+            Self::Terminal => Self::Logs,
+            Self::Logs => Self::Side,
+        }
+    }
+}
+
+/// 확대할 때 기준이 되는 패널 (포커스가 있는 쪽)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Panel {
+    Terminal,
+    Logs,
+}
+
+/// 각 패널의 화면 위치. 보이지 않는 패널은 크기가 0이다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutInfo {
+    pub header: Rect,
+    pub terminal: Rect,
+    pub logs: Rect,
+    pub input: Rect,
+    pub term_inner: Rect,
+}
+
+impl LayoutInfo {
+    /// PTY에 맞출 (행, 열). 터미널 패널이 숨겨져 있으면 None (쉘은 이전 크기 유지).
+    pub fn pty_size(&self) -> Option<(u16, u16)> {
+        (!self.term_inner.is_empty()).then_some((self.term_inner.height, self.term_inner.width))
+    }
+}
+
+/// 사용자가 고른 배치. 배치와 비율은 설정(`ui.layout`, `ui.split`)에서 읽고,
+/// 확대는 이번 실행에서만 유지한다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanelLayout {
+    pub preset: Preset,
+    /// 터미널 패널이 본문에서 차지하는 비율(%)
+    pub split: u16,
+    /// 포커스가 있는 패널만 본문 전체에 표시
+    pub zoom: bool,
+}
+
+impl PanelLayout {
+    pub fn from_config(ui: &UiConfig) -> Result<Self, String> {
+        let preset = Preset::from_setting(&ui.layout).ok_or_else(|| {
+            tr!(
+                "config.invalid_layout",
+                key = "ui.layout",
+                value = ui.layout
+            )
+        })?;
+        Ok(Self {
+            preset,
+            split: ui.split.clamp(SPLIT_MIN, SPLIT_MAX),
+            zoom: false,
+        })
+    }
+
+    /// 터미널 패널을 키운다 (터미널만/로그만 배치에서는 나란히 배치로 돌아가서)
+    pub fn grow(&mut self) {
+        self.show_both();
+        self.split = (self.split + SPLIT_STEP).min(SPLIT_MAX);
+    }
+
+    pub fn shrink(&mut self) {
+        self.show_both();
+        self.split = self.split.saturating_sub(SPLIT_STEP).max(SPLIT_MIN);
+    }
+
+    fn show_both(&mut self) {
+        self.zoom = false;
+        if matches!(self.preset, Preset::Terminal | Preset::Logs) {
+            self.preset = Preset::Side;
+        }
+    }
+
+    /// 터미널이 숨겨진 배치에서 터미널로 포커스가 가면 설정은 그대로 두고 확대해서 보여 준다
+    pub fn reveal_terminal(&mut self) {
+        if !self.shows_terminal(Panel::Terminal) {
+            self.zoom = true;
+        }
+    }
+
+    /// 터미널 패널이 보이는지 (포커스가 가도 되는지)
+    pub fn shows_terminal(&self, focus: Panel) -> bool {
+        self.visible(focus).0
+    }
+
+    /// (터미널, 로그 목록) 표시 여부
+    fn visible(&self, focus: Panel) -> (bool, bool) {
+        if self.zoom {
+            return (focus == Panel::Terminal, focus == Panel::Logs);
+        }
+        match self.preset {
+            Preset::Side | Preset::Stacked => (true, true),
+            Preset::Terminal => (true, false),
+            Preset::Logs => (false, true),
+        }
+    }
+
+    pub fn compute(&self, area: Rect, focus: Panel) -> LayoutInfo {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(3),
+            ])
+            .split(area);
+        let body = chunks[1];
+        let hidden = Rect {
+            width: 0,
+            height: 0,
+            ..body
+        };
+
+        let (terminal, logs) = match self.visible(focus) {
+            (true, false) => (body, hidden),
+            (false, true) => (hidden, body),
+            _ => {
+                let direction = match self.preset {
+                    Preset::Stacked => Direction::Vertical,
+                    _ => Direction::Horizontal,
+                };
+                let parts = Layout::default()
+                    .direction(direction)
+                    .constraints([
+                        Constraint::Percentage(self.split),
+                        Constraint::Percentage(100 - self.split),
+                    ])
+                    .split(body);
+                (parts[0], parts[1])
+            }
+        };
+
+        let term_inner = Rect {
+            x: terminal.x.saturating_add(1),
+            y: terminal.y.saturating_add(1),
+            width: terminal.width.saturating_sub(2),
+            height: terminal.height.saturating_sub(2),
+        };
+
+        LayoutInfo {
+            header: chunks[0],
+            terminal,
+            logs,
+            input: chunks[2],
+            term_inner,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(preset: Preset, split: u16) -> PanelLayout {
+        PanelLayout {
+            preset,
+            split,
+            zoom: false,
+        }
+    }
+
+    #[test]
+    fn presets_split_the_body() {
+        let area = Rect::new(0, 0, 100, 46);
+
+        let side = layout(Preset::Side, 60).compute(area, Panel::Terminal);
+        assert_eq!(side.terminal, Rect::new(0, 3, 60, 40));
+        assert_eq!(side.logs, Rect::new(60, 3, 40, 40));
+        assert_eq!(side.pty_size(), Some((38, 58)));
+
+        let stacked = layout(Preset::Stacked, 25).compute(area, Panel::Logs);
+        assert_eq!(stacked.terminal, Rect::new(0, 3, 100, 10));
+        assert_eq!(stacked.logs, Rect::new(0, 13, 100, 30));
+        assert_eq!(stacked.input, Rect::new(0, 43, 100, 3));
+
+        let logs = layout(Preset::Logs, 60).compute(area, Panel::Terminal);
+        assert_eq!(logs.logs, Rect::new(0, 3, 100, 40));
+        assert!(logs.terminal.is_empty());
+        assert_eq!(logs.pty_size(), None);
+    }
+
+    #[test]
+    fn zoom_follows_focus_and_resizing_shows_both() {
+        let area = Rect::new(0, 0, 100, 46);
+        let mut panels = layout(Preset::Stacked, 60);
+        panels.zoom = true;
+        assert_eq!(panels.compute(area, Panel::Logs).logs.height, 40);
+        assert!(!panels.shows_terminal(Panel::Logs));
+        assert_eq!(panels.compute(area, Panel::Terminal).terminal.height, 40);
+
+        panels.grow();
+        assert_eq!((panels.zoom, panels.split), (false, 65));
+        let mut panels = layout(Preset::Terminal, 20);
+        panels.shrink();
+        assert_eq!((panels.preset, panels.split), (Preset::Side, 20));
+        for _ in 0..20 {
+            panels.grow();
+        }
+        assert_eq!(panels.split, 80);
+    }
+}
@@ -1,2 +1,13 @@
+pub mod cast;
+pub mod keyboard;
+pub mod keymap;
+pub mod layout;
+pub mod mouse;
 pub mod pty_terminal;
+pub mod selection;
+pub mod shell_integration;
+pub mod tabs;
+pub mod term_render;
+pub mod term_search;
 pub mod tui;
+pub mod watch;
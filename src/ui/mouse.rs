@@ -0,0 +1,210 @@
+use crossterm::event::{KeyModifiers, MouseButton, MouseEventKind};
+use vt100::{MouseProtocolEncoding, MouseProtocolMode};
+
+/// Button code for "no button" in motion and legacy release reports.
+const NO_BUTTON: u8 = 3;
+const MOTION: u8 = 32;
+const WHEEL: u8 = 64;
+
+/// Encodes a mouse event at `col`/`row` (0-based, relative to the terminal screen) the way
+/// xterm reports it for the mode the child enabled. Returns `None` when the mode does not
+/// report this kind of event, or the position cannot be expressed in the legacy encoding.
+pub fn encode_mouse_event(
+    kind: MouseEventKind,
+    modifiers: KeyModifiers,
+    col: u16,
+    row: u16,
+    mode: MouseProtocolMode,
+    encoding: MouseProtocolEncoding,
+) -> Option<Vec<u8>> {
+    use MouseProtocolMode as Mode;
+
+    let (button, release) = match kind {
+        MouseEventKind::Down(button) => (button_code(button), false),
+        MouseEventKind::Up(button) => match mode {
+            Mode::None | Mode::Press => return None,
+            _ => (button_code(button), true),
+        },
+        MouseEventKind::Drag(button) => match mode {
+            Mode::ButtonMotion | Mode::AnyMotion => (button_code(button) + MOTION, false),
+            _ => return None,
+        },
+        MouseEventKind::Moved => match mode {
+            Mode::AnyMotion => (NO_BUTTON + MOTION, false),
+            _ => return None,
+        },
+        MouseEventKind::ScrollUp => (WHEEL, false),
+        MouseEventKind::ScrollDown => (WHEEL + 1, false),
+        MouseEventKind::ScrollLeft => (WHEEL + 2, false),
+        MouseEventKind::ScrollRight => (WHEEL + 3, false),
+    };
+    if mode == Mode::None {
+        return None;
+    }
+    // X10 mode reports no modifiers.
+    let button = if mode == Mode::Press {
+        button
+    } else {
+        button | modifier_bits(modifiers)
+    };
+
+    let (x, y) = (u32::from(col) + 1, u32::from(row) + 1);
+    match encoding {
+        MouseProtocolEncoding::Sgr => Some(
+            format!(
+                "\x1b[<{};{};{}{}",
+                button,
+                x,
+                y,
+                if release { 'm' } else { 'M' }
+            )
+            .into_bytes(),
+        ),
+        MouseProtocolEncoding::Default | MouseProtocolEncoding::Utf8 => {
+            // The legacy encodings cannot tell which button was released.
+            let button = if release {
+                NO_BUTTON | (button & !NO_BUTTON)
+            } else {
+                button
+            };
+            let mut bytes = b"\x1b[M".to_vec();
+            bytes.push(32 + button);
+            for value in [x, y] {
+                let value = value + 32;
+                if encoding == MouseProtocolEncoding::Utf8 {
+                    let mut buf = [0; 4];
+                    bytes.extend(char::from_u32(value)?.encode_utf8(&mut buf).as_bytes());
+                } else {
+                    bytes.push(u8::try_from(value).ok()?);
+                }
+            }
+            Some(bytes)
+        }
+    }
+}
+
+fn button_code(button: MouseButton) -> u8 {
+    match button {
+        MouseButton::Left => 0,
+        MouseButton::Middle => 1,
+        MouseButton::Right => 2,
+    }
+}
+
+fn modifier_bits(modifiers: KeyModifiers) -> u8 {
+    let mut bits = 0;
+    if modifiers.contains(KeyModifiers::SHIFT) {
+        bits |= 4;
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        bits |= 8;
+    }
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        bits |= 16;
+    }
+    bits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use MouseEventKind::*;
+    use MouseProtocolEncoding as Enc;
+    use MouseProtocolMode as Mode;
+
+    const NONE: KeyModifiers = KeyModifiers::NONE;
+
+    fn encode(
+        kind: MouseEventKind,
+        modifiers: KeyModifiers,
+        col: u16,
+        row: u16,
+        mode: Mode,
+        encoding: Enc,
+    ) -> Option<String> {
+        encode_mouse_event(kind, modifiers, col, row, mode, encoding)
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    #[test]
+    fn sgr_reports() {
+        let cases = [
+            (Down(MouseButton::Left), NONE, "\x1b[<0;1;1M"),
+            (Up(MouseButton::Left), NONE, "\x1b[<0;1;1m"),
+            (Down(MouseButton::Right), NONE, "\x1b[<2;1;1M"),
+            (Up(MouseButton::Middle), NONE, "\x1b[<1;1;1m"),
+            (Drag(MouseButton::Left), NONE, "\x1b[<32;1;1M"),
+            (Moved, NONE, "\x1b[<35;1;1M"),
+            (ScrollUp, NONE, "\x1b[<64;1;1M"),
+            (ScrollDown, NONE, "\x1b[<65;1;1M"),
+            (
+                Down(MouseButton::Left),
+                KeyModifiers::CONTROL,
+                "\x1b[<16;1;1M",
+            ),
+            (
+                Down(MouseButton::Left),
+                KeyModifiers::SHIFT | KeyModifiers::ALT,
+                "\x1b[<12;1;1M",
+            ),
+        ];
+        for (kind, modifiers, expected) in cases {
+            assert_eq!(
+                encode(kind, modifiers, 0, 0, Mode::AnyMotion, Enc::Sgr).as_deref(),
+                Some(expected),
+                "{kind:?} {modifiers:?}"
+            );
+        }
+        assert_eq!(
+            encode(
+                Down(MouseButton::Left),
+                NONE,
+                299,
+                99,
+                Mode::PressRelease,
+                Enc::Sgr
+            )
+            .as_deref(),
+            Some("\x1b[<0;300;100M")
+        );
+    }
+
+    #[test]
+    fn mode_filters_events() {
+        let reported = |kind, mode| encode(kind, NONE, 4, 2, mode, Enc::Sgr).is_some();
+        let drag = Drag(MouseButton::Left);
+        let up = Up(MouseButton::Left);
+
+        assert!(!reported(Down(MouseButton::Left), Mode::None));
+        assert!(!reported(ScrollUp, Mode::None));
+        assert!(reported(Down(MouseButton::Left), Mode::Press));
+        assert!(!reported(up, Mode::Press));
+        assert!(reported(up, Mode::PressRelease));
+        assert!(!reported(drag, Mode::PressRelease));
+        assert!(reported(drag, Mode::ButtonMotion));
+        assert!(!reported(Moved, Mode::ButtonMotion));
+        assert!(reported(Moved, Mode::AnyMotion));
+        assert!(reported(ScrollDown, Mode::Press));
+    }
+
+    #[test]
+    fn legacy_encodings() {
+        let at = |col, row, kind, encoding| {
+            encode_mouse_event(kind, NONE, col, row, Mode::PressRelease, encoding)
+        };
+        assert_eq!(
+            at(0, 0, Down(MouseButton::Left), Enc::Default),
+            Some(b"\x1b[M\x20\x21\x21".to_vec())
+        );
+        // Release is reported as button 3.
+        assert_eq!(
+            at(9, 4, Up(MouseButton::Right), Enc::Default),
+            Some(b"\x1b[M\x23\x2a\x25".to_vec())
+        );
+        // Beyond column 223 the byte encoding overflows; UTF-8 mode keeps going.
+        assert_eq!(at(230, 0, Down(MouseButton::Left), Enc::Default), None);
+        let utf8 = at(230, 0, Down(MouseButton::Left), Enc::Utf8).unwrap();
+        assert_eq!(utf8, "\x1b[M\x20\u{107}\x21".as_bytes());
+    }
+}
@@ -1,25 +1,89 @@
 use std::{
-    io::{Read, Write},
-    path::PathBuf,
+    io::{ErrorKind, Read, Write},
+    path::{Path, PathBuf},
     sync::mpsc::{self, Receiver, Sender},
-    thread,
-    time::Duration,
+    thread::{self, JoinHandle},
 };
 
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
-use portable_pty::{CommandBuilder, MasterPty, PtyPair, PtySize, native_pty_system};
-use vt100::Parser;
+use crossterm::event::{KeyEvent, KeyModifiers, MouseEventKind};
+use portable_pty::{CommandBuilder, ExitStatus, MasterPty, PtyPair, PtySize, native_pty_system};
+use tempfile::TempDir;
+use vt100::{MouseProtocolMode, Parser, Screen};
+
+use crate::config::ShellConfig;
+use crate::i18n::tr;
+use crate::ui::cast::CastRecording;
+use crate::ui::keyboard::{KeyModes, encode_key_event, encode_paste};
+use crate::ui::mouse::encode_mouse_event;
+use crate::ui::selection::{self, CellPos, Selection};
+use crate::ui::shell_integration::{self, CommandRecord, CommandTracker};
+use crate::ui::term_search::{self, SearchMatch};
+
+/// What runs in the PTY: the program, its arguments and the environment it gets on top of ours.
+#[derive(Debug, Clone)]
+pub struct ShellLaunch {
+    pub program: String,
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+}
+
+impl ShellLaunch {
+    /// `shell.command` (or `$SHELL`, or bash) with `shell.env` applied after the defaults.
+    pub fn from_config(config: &ShellConfig) -> Result<Self, String> {
+        let mut words = split_words(&config.command)?;
+        let program = if words.is_empty() {
+            std::env::var("SHELL").unwrap_or_else(|_| "bash".to_string())
+        } else {
+            words.remove(0)
+        };
+        // The renderer draws 256 colours and 24-bit colour, like xterm.
+        let mut launch = Self {
+            program,
+            args: words,
+            env: vec![
+                ("TERM".to_string(), "xterm-256color".to_string()),
+                ("COLORTERM".to_string(), "truecolor".to_string()),
+            ],
+        };
+        for entry in split_words(&config.env)? {
+            match entry.split_once('=') {
+                Some((name, value)) if !name.is_empty() => launch.set_env(name, value),
+                _ => return Err(tr!("shell.bad_env", entry = entry)),
+            }
+        }
+        Ok(launch)
+    }
+
+    /// Sets a variable, replacing an earlier value of the same name.
+    pub fn set_env(&mut self, name: &str, value: &str) {
+        self.env.retain(|(existing, _)| existing != name);
+        self.env.push((name.to_string(), value.to_string()));
+    }
+}
 
 /// Owns the PTY handles and moves raw bytes between the shell and the UI.
 pub struct PtyShell {
     master: Box<dyn MasterPty + Send>,
     writer: Box<dyn Write + Send>,
     rx: Receiver<Vec<u8>>,
-    _child: Box<dyn portable_pty::Child + Send>,
+    child: Box<dyn portable_pty::Child + Send>,
+    /// Whether the shell was started with the command boundary hooks.
+    integrated: bool,
+    /// Hook scripts the shell was started with, removed once the shell is gone.
+    _scripts: Option<TempDir>,
+    /// Ends by itself once the shell has exited or this handle is dropped; only tests join it.
+    #[cfg_attr(not(test), allow(dead_code))]
+    reader: Option<JoinHandle<()>>,
 }
 
 impl PtyShell {
-    pub fn spawn(repo_root: PathBuf, rows: u16, cols: u16) -> Result<Self, String> {
+    pub fn spawn(
+        cwd: PathBuf,
+        rows: u16,
+        cols: u16,
+        launch: &ShellLaunch,
+        integration: bool,
+    ) -> Result<Self, String> {
         let size = PtySize {
             rows,
             cols,
@@ -28,34 +92,48 @@ impl PtyShell {
         };
         let pair: PtyPair = native_pty_system()
             .openpty(size)
-            .map_err(|e| format!("openpty failed: {e}"))?;
+            .map_err(|e| tr!("pty.open_failed", error = e))?;
 
-        let shell = std::env::var("SHELL").unwrap_or_else(|_| "bash".to_string());
-        let mut cmd = CommandBuilder::new(shell);
-        cmd.cwd(repo_root);
+        let mut cmd = CommandBuilder::new(&launch.program);
+        cmd.cwd(cwd);
+        // These describe the terminal we run in, not the one we draw.
+        cmd.env_remove("TERM_PROGRAM");
+        cmd.env_remove("TERM_PROGRAM_VERSION");
+        for (name, value) in &launch.env {
+            cmd.env(name, value);
+        }
+        // Unsupported shells just run without hooks.
+        let scripts = integration
+            .then(|| shell_integration::inject(&mut cmd, &launch.program).ok())
+            .flatten();
+        let integrated = scripts.is_some();
+        cmd.args(&launch.args);
 
         let child = pair
             .slave
             .spawn_command(cmd)
-            .map_err(|e| format!("spawn shell failed: {e}"))?;
+            .map_err(|e| tr!("pty.spawn_failed", error = e))?;
 
         let reader = pair
             .master
             .try_clone_reader()
-            .map_err(|e| format!("clone reader failed: {e}"))?;
+            .map_err(|e| tr!("pty.reader_failed", error = e))?;
         let (tx, rx) = mpsc::channel();
-        spawn_reader_thread(reader, tx);
+        let reader = spawn_reader_thread(reader, tx);
 
         let master = pair.master;
         let writer = master
             .take_writer()
-            .map_err(|e| format!("take_writer failed: {e}"))?;
+            .map_err(|e| tr!("pty.writer_failed", error = e))?;
 
         Ok(Self {
             master,
             writer,
             rx,
-            _child: child,
+            child,
+            integrated,
+            _scripts: scripts,
+            reader: Some(reader),
         })
     }
 
@@ -76,12 +154,62 @@ impl PtyShell {
     pub fn try_read(&mut self) -> Option<Vec<u8>> {
         self.rx.try_recv().ok()
     }
+
+    /// Exit status once the shell has exited; never blocks.
+    pub fn try_wait(&mut self) -> Option<ExitStatus> {
+        self.child.try_wait().ok().flatten()
+    }
+
+    /// The shell's current directory, where the OS lets us read it.
+    pub fn cwd(&self) -> Option<PathBuf> {
+        if cfg!(target_os = "linux") {
+            let pid = self.child.process_id()?;
+            std::fs::read_link(format!("/proc/{pid}/cwd")).ok()
+        } else {
+            None
+        }
+    }
+}
+
+impl Drop for PtyShell {
+    /// Closing a tab must not leave its shell running without a terminal, or a zombie
+    /// behind until we exit.
+    fn drop(&mut self) {
+        if self.try_wait().is_none() {
+            let _ = self.child.kill();
+            // The shell was killed, so this returns right away.
+            let _ = self.child.wait();
+        }
+    }
+}
+
+/// Rows kept above the screen; once full, the oldest row is dropped for every new one.
+const SCROLLBACK_LINES: usize = 10_000;
+
+/// Keeps what vt100 reports only through callbacks.
+#[derive(Debug, Default)]
+struct Window {
+    title: String,
+}
+
+impl vt100::Callbacks for Window {
+    fn set_window_title(&mut self, _: &mut Screen, title: &[u8]) {
+        self.title = String::from_utf8_lossy(title).into_owned();
+    }
 }
 
 /// High-level terminal abstraction that keeps PTY I/O separate from UI rendering.
 pub struct PtyTerminal {
     shell: PtyShell,
-    parser: Parser,
+    parser: Parser<Window>,
+    tracker: Option<CommandTracker>,
+    /// Set once the shell has exited; the screen keeps its last output.
+    exit: Option<ExitStatus>,
+    /// Last directory seen for the shell, to restart it there.
+    cwd: Option<PathBuf>,
+    recording: Option<CastRecording>,
+    /// Output arrived while the scrollback was full, so history rows may have moved up.
+    history_trimmed: bool,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -92,10 +220,25 @@ pub struct CursorState {
 }
 
 impl PtyTerminal {
-    pub fn spawn(repo_root: PathBuf, rows: u16, cols: u16) -> Result<Self, String> {
+    pub fn spawn(
+        repo_root: PathBuf,
+        rows: u16,
+        cols: u16,
+        config: &ShellConfig,
+        launch: &ShellLaunch,
+    ) -> Result<Self, String> {
+        let shell = PtyShell::spawn(repo_root, rows, cols, launch, config.integration)?;
+        let tracker = shell
+            .integrated
+            .then(|| CommandTracker::new(config.output_lines));
         Ok(Self {
-            shell: PtyShell::spawn(repo_root, rows, cols)?,
-            parser: Parser::new(rows, cols, 10_000),
+            shell,
+            parser: Parser::new_with_callbacks(rows, cols, SCROLLBACK_LINES, Window::default()),
+            tracker,
+            exit: None,
+            cwd: None,
+            recording: None,
+            history_trimmed: false,
         })
     }
 
@@ -105,35 +248,135 @@ impl PtyTerminal {
         if current_rows != rows || current_cols != cols {
             let offset = self.scroll_offset();
             self.shell.resize(rows, cols);
-            self.parser.set_size(rows, cols);
-            self.parser.set_scrollback(offset);
+            self.parser.screen_mut().set_size(rows, cols);
+            self.parser.screen_mut().set_scrollback(offset);
+            if let Some(recording) = self.recording.as_mut() {
+                recording.resize(rows, cols);
+            }
         }
     }
 
     pub fn send_bytes(&mut self, bytes: &[u8]) {
         // Only follow the live view when already at the bottom; keep sticky scroll otherwise.
         if self.scroll_offset() == 0 {
-            self.parser.set_scrollback(0);
+            self.parser.screen_mut().set_scrollback(0);
         }
         self.shell.write(bytes);
     }
 
-    pub fn poll_output(&mut self) {
+    /// Sends a key the way xterm would, honouring the cursor mode the child set.
+    pub fn send_key(&mut self, key: KeyEvent) {
+        let modes = KeyModes::from_screen(self.parser.screen());
+        if let Some(bytes) = encode_key_event(key, modes) {
+            self.send_bytes(&bytes);
+        }
+    }
+
+    /// Sends pasted text, bracketed if the child enabled bracketed paste.
+    pub fn paste(&mut self, text: &str) {
+        let bytes = encode_paste(text, self.parser.screen().bracketed_paste());
+        self.send_bytes(&bytes);
+    }
+
+    /// Returns the last shell command that finished during this poll (shell integration only).
+    pub fn poll_output(&mut self) -> Option<CommandRecord> {
+        let mut finished = None;
+        let cols = self.size().1;
+        // Checked before draining so output written just before the exit is still shown.
+        let exit = match self.exit {
+            None => self.shell.try_wait(),
+            Some(_) => None,
+        };
+        // vt100 does not say how many rows it dropped, so any output counts as a trim.
+        let full = selection::scrollback_len(self.parser.screen_mut()) >= SCROLLBACK_LINES;
+        let mut received = false;
         while let Some(bytes) = self.shell.try_read() {
+            received = true;
             // Preserve raw stream; vt100 handles control sequences internally.
             self.parser.process(&bytes);
+            if let Some(recording) = self.recording.as_mut() {
+                recording.output(&bytes);
+            }
+            if let Some(tracker) = self.tracker.as_mut()
+                && let Some(record) = tracker.feed(&bytes, cols)
+            {
+                finished = Some(record);
+            }
+        }
+        // A prompt usually follows `cd`, so output is a good moment to look again.
+        if received && exit.is_none() && self.exit.is_none() {
+            self.cwd = self.shell.cwd().or(self.cwd.take());
         }
+        if exit.is_some() {
+            self.exit = exit;
+        }
+        self.history_trimmed |= received && full;
+        finished
     }
 
-    pub fn lines(&self) -> Vec<String> {
-        let screen = self.parser.screen();
-        let (_rows, cols) = screen.size();
-        screen.rows(0, cols).collect()
+    /// Whether history rows may have shifted since the last call, which makes stored
+    /// [`CellPos`] and [`SearchMatch`] rows point at the wrong lines.
+    pub fn take_history_trimmed(&mut self) -> bool {
+        std::mem::take(&mut self.history_trimmed)
+    }
+
+    /// `Some` once the shell has exited.
+    pub fn exit_status(&self) -> Option<&ExitStatus> {
+        self.exit.as_ref()
+    }
+
+    /// Last known directory of the shell, if it could be read.
+    pub fn last_cwd(&self) -> Option<&PathBuf> {
+        self.cwd.as_ref()
+    }
+
+    /// Starts writing the output to an asciicast file at `path`, beginning with the live screen.
+    pub fn start_recording(&mut self, path: &Path) -> Result<(), String> {
+        let offset = self.scroll_offset();
+        self.parser.screen_mut().set_scrollback(0);
+        let screen = self.parser.screen().contents_formatted();
+        self.parser.screen_mut().set_scrollback(offset);
+        let (rows, cols) = self.size();
+        self.recording = Some(CastRecording::create(path, rows, cols, &screen)?);
+        Ok(())
+    }
+
+    /// Ends the recording, if any, and returns the finished file.
+    pub fn stop_recording(&mut self) -> Option<Result<PathBuf, String>> {
+        self.recording.take().map(CastRecording::finish)
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Moves a recording over from a tab whose shell is being replaced.
+    pub fn take_recording(&mut self) -> Option<CastRecording> {
+        self.recording.take()
+    }
+
+    pub fn continue_recording(&mut self, recording: CastRecording) {
+        self.recording = Some(recording);
+    }
+
+    /// `None` when shell integration is off or unsupported for this shell.
+    pub fn command_tracker(&self) -> Option<&CommandTracker> {
+        self.tracker.as_ref()
+    }
+
+    /// Visible screen (scrollback offset applied) for rendering.
+    pub fn screen(&self) -> &Screen {
+        self.parser.screen()
+    }
+
+    /// Window title set by the shell or the program running in it.
+    pub fn title(&self) -> &str {
+        &self.parser.callbacks().title
     }
 
     pub fn cursor_state(&self) -> Option<CursorState> {
         let screen = self.parser.screen();
-        if self.scroll_offset() > 0 || screen.hide_cursor() {
+        if self.exit.is_some() || self.scroll_offset() > 0 || screen.hide_cursor() {
             return None;
         }
         let (row, col) = screen.cursor_position();
@@ -146,12 +389,12 @@ impl PtyTerminal {
 
     pub fn scroll_up(&mut self, lines: usize) {
         let offset = self.scroll_offset().saturating_add(lines);
-        self.parser.set_scrollback(offset);
+        self.parser.screen_mut().set_scrollback(offset);
     }
 
     pub fn scroll_down(&mut self, lines: usize) {
         let offset = self.scroll_offset().saturating_sub(lines);
-        self.parser.set_scrollback(offset);
+        self.parser.screen_mut().set_scrollback(offset);
     }
 
     pub fn scroll_offset(&self) -> usize {
@@ -165,88 +408,212 @@ impl PtyTerminal {
     pub fn alternate_screen(&self) -> bool {
         self.parser.screen().alternate_screen()
     }
+
+    /// The child enabled xterm mouse reporting (vim, htop, `less --mouse`, ...).
+    pub fn mouse_reporting(&self) -> bool {
+        self.parser.screen().mouse_protocol_mode() != MouseProtocolMode::None
+    }
+
+    /// Reports a mouse event at screen cell `col`/`row` in the encoding the child asked for.
+    pub fn send_mouse(
+        &mut self,
+        kind: MouseEventKind,
+        modifiers: KeyModifiers,
+        col: u16,
+        row: u16,
+    ) {
+        let screen = self.parser.screen();
+        if let Some(bytes) = encode_mouse_event(
+            kind,
+            modifiers,
+            col,
+            row,
+            screen.mouse_protocol_mode(),
+            screen.mouse_protocol_encoding(),
+        ) {
+            self.send_bytes(&bytes);
+        }
+    }
+
+    /// Every hit of `query` in the history, oldest first.
+    pub fn search(&mut self, query: &str) -> Vec<SearchMatch> {
+        term_search::find_matches(self.parser.screen_mut(), query)
+    }
+
+    /// Scrollback rows plus screen rows.
+    pub fn history_rows(&mut self) -> usize {
+        selection::history_rows(self.parser.screen_mut())
+    }
+
+    pub fn view_to_history(&mut self, row: u16, col: u16) -> CellPos {
+        selection::view_to_history(self.parser.screen_mut(), row, col)
+    }
+
+    pub fn history_to_view(&mut self, row: usize) -> Option<u16> {
+        selection::history_to_view(self.parser.screen_mut(), row)
+    }
+
+    pub fn scroll_into_view(&mut self, row: usize) {
+        selection::scroll_into_view(self.parser.screen_mut(), row);
+    }
+
+    pub fn selected_text(&mut self, selection: &Selection) -> String {
+        selection::selected_text(self.parser.screen_mut(), selection)
+    }
 }
 
-fn spawn_reader_thread(mut reader: Box<dyn Read + Send>, tx: Sender<Vec<u8>>) {
+/// Runs until the shell side of the PTY is gone (EOF or EIO) or the shell handle is dropped.
+fn spawn_reader_thread(mut reader: Box<dyn Read + Send>, tx: Sender<Vec<u8>>) -> JoinHandle<()> {
     thread::spawn(move || {
         let mut buf = [0u8; 4096];
         loop {
             match reader.read(&mut buf) {
                 Ok(0) => break,
                 Ok(n) => {
-                    let _ = tx.send(buf[..n].to_vec());
+                    if tx.send(buf[..n].to_vec()).is_err() {
+                        break;
+                    }
                 }
-                Err(_) => thread::sleep(Duration::from_millis(5)),
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) => break,
             }
         }
-    });
+    })
 }
 
-/// Converts crossterm key events into the raw byte sequences expected by a PTY-backed shell.
-pub fn encode_key_event(key: KeyEvent) -> Option<Vec<u8>> {
-    if !matches!(key.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
-        return None;
-    }
-
-    match key.code {
-        KeyCode::Char(c) => {
-            let mut bytes = if key.modifiers.contains(KeyModifiers::CONTROL) {
-                let upper = c.to_ascii_uppercase();
-                let ctrl = match upper {
-                    '@' => 0x00,
-                    'A'..='Z' => upper as u8 - b'A' + 1,
-                    '[' => 0x1b,
-                    '\\' => 0x1c,
-                    ']' => 0x1d,
-                    '^' | '6' => 0x1e,
-                    '_' => 0x1f,
-                    ' ' => 0x00,
-                    _ => return None,
-                };
-                vec![ctrl]
-            } else {
-                c.to_string().into_bytes()
-            };
-
-            if key.modifiers.contains(KeyModifiers::ALT) {
-                bytes.insert(0, 0x1b);
+/// Splits a command line into words like a POSIX shell does for plain words: whitespace
+/// separates, single quotes are literal, double quotes and backslashes escape.
+fn split_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
             }
-            Some(bytes)
-        }
-        KeyCode::Enter => Some(vec![b'\r']),
-        KeyCode::Backspace => Some(vec![0x7f]),
-        KeyCode::Tab => Some(vec![b'\t']),
-        KeyCode::BackTab => Some(b"\x1b[Z".to_vec()),
-        KeyCode::Esc => Some(vec![0x1b]),
-        KeyCode::Left => Some(b"\x1b[D".to_vec()),
-        KeyCode::Right => Some(b"\x1b[C".to_vec()),
-        KeyCode::Up => Some(b"\x1b[A".to_vec()),
-        KeyCode::Down => Some(b"\x1b[B".to_vec()),
-        KeyCode::Home => Some(b"\x1b[H".to_vec()),
-        KeyCode::End => Some(b"\x1b[F".to_vec()),
-        KeyCode::PageUp => Some(b"\x1b[5~".to_vec()),
-        KeyCode::PageDown => Some(b"\x1b[6~".to_vec()),
-        KeyCode::Delete => Some(b"\x1b[3~".to_vec()),
-        KeyCode::Insert => Some(b"\x1b[2~".to_vec()),
-        KeyCode::F(n) if (1..=12).contains(&n) => {
-            // Match common xterm sequences for F1-F12.
-            let seq = match n {
-                1 => "\x1bOP",
-                2 => "\x1bOQ",
-                3 => "\x1bOR",
-                4 => "\x1bOS",
-                5 => "\x1b[15~",
-                6 => "\x1b[17~",
-                7 => "\x1b[18~",
-                8 => "\x1b[19~",
-                9 => "\x1b[20~",
-                10 => "\x1b[21~",
-                11 => "\x1b[23~",
-                12 => "\x1b[24~",
-                _ => return None,
-            };
-            Some(seq.as_bytes().to_vec())
-        }
-        _ => None,
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err(tr!("shell.bad_quote", line = line)),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err(tr!("shell.bad_quote", line = line)),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err(tr!("shell.bad_quote", line = line)),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                if let Some(c) = chars.next() {
+                    word.push(c);
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    #[test]
+    fn splits_words_with_quotes() {
+        let cases: [(&str, &[&str]); 7] = [
+            ("", &[]),
+            ("  tmux   attach ", &["tmux", "attach"]),
+            ("nix develop -c zsh", &["nix", "develop", "-c", "zsh"]),
+            ("EDITOR='code --wait'", &["EDITOR=code --wait"]),
+            (r#"a "b \"c\" \x" ''"#, &["a", r#"b "c" \x"#, ""]),
+            (r"one\ word", &["one word"]),
+            ("x=\"\"y", &["x=y"]),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(split_words(line).unwrap(), expected, "{line:?}");
+        }
+        assert!(split_words("echo 'open").is_err());
+        assert!(split_words("echo \"open").is_err());
+    }
+
+    #[test]
+    fn launch_from_config() {
+        let config = ShellConfig {
+            command: "tmux new -A -s 'my work'".to_string(),
+            env: "RUST_LOG=debug TERM=screen-256color EMPTY=".to_string(),
+            ..ShellConfig::default()
+        };
+        let mut launch = ShellLaunch::from_config(&config).unwrap();
+        assert_eq!(launch.program, "tmux");
+        assert_eq!(launch.args, ["new", "-A", "-s", "my work"]);
+        launch.set_env("BBIRIBARABU_BRANCH", "main");
+        launch.set_env("BBIRIBARABU_BRANCH", "feature/login");
+        let env = |name: &str| {
+            launch
+                .env
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.as_str())
+        };
+        assert_eq!(env("TERM"), Some("screen-256color"));
+        assert_eq!(env("COLORTERM"), Some("truecolor"));
+        assert_eq!(env("RUST_LOG"), Some("debug"));
+        assert_eq!(env("EMPTY"), Some(""));
+        assert_eq!(env("BBIRIBARABU_BRANCH"), Some("feature/login"));
+
+        let bad = ShellConfig {
+            env: "=oops".to_string(),
+            ..ShellConfig::default()
+        };
+        assert!(ShellLaunch::from_config(&bad).is_err());
+    }
+
+    #[test]
+    fn reader_thread_ends_after_shell_exits() {
+        let launch = ShellLaunch {
+            program: "sh".to_string(),
+            args: vec!["-c".to_string(), "exit".to_string()],
+            env: Vec::new(),
+        };
+        let mut shell = PtyShell::spawn(std::env::temp_dir(), 24, 80, &launch, false).unwrap();
+        let reader = shell.reader.take().unwrap();
+        drop(shell);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !reader.is_finished() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(reader.is_finished());
+        reader.join().unwrap();
     }
 }
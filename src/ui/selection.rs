@@ -0,0 +1,200 @@
+use vt100::Screen;
+
+/// A cell in the terminal history. `row` counts from the oldest scrollback line,
+/// so it stays put while new output scrolls the view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CellPos {
+    pub row: usize,
+    pub col: u16,
+}
+
+/// Stream selection over the terminal history (like a terminal emulator's mouse selection).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Selection {
+    /// Where the selection started; `None` while only the cursor is placed (keyboard mode).
+    pub anchor: Option<CellPos>,
+    pub cursor: CellPos,
+}
+
+impl Selection {
+    pub fn at(cursor: CellPos) -> Self {
+        Self {
+            anchor: None,
+            cursor,
+        }
+    }
+
+    /// Ordered, inclusive bounds. Without an anchor this is the cursor cell.
+    pub fn bounds(&self) -> (CellPos, CellPos) {
+        let anchor = self.anchor.unwrap_or(self.cursor);
+        (anchor.min(self.cursor), anchor.max(self.cursor))
+    }
+
+    /// Column range selected on `row` (end exclusive), clipped to `cols`.
+    pub fn columns_on(&self, row: usize, cols: u16) -> Option<(u16, u16)> {
+        let (start, end) = self.bounds();
+        if row < start.row || row > end.row {
+            return None;
+        }
+        let from = if row == start.row { start.col } else { 0 };
+        let to = if row == end.row {
+            end.col.saturating_add(1)
+        } else {
+            cols
+        };
+        (from < to.min(cols)).then_some((from, to.min(cols)))
+    }
+}
+
+/// Number of rows currently held in the scrollback buffer.
+pub fn scrollback_len(screen: &mut Screen) -> usize {
+    let offset = screen.scrollback();
+    screen.set_scrollback(usize::MAX);
+    let len = screen.scrollback();
+    screen.set_scrollback(offset);
+    len
+}
+
+/// Total number of history rows (scrollback plus the screen).
+pub fn history_rows(screen: &mut Screen) -> usize {
+    scrollback_len(screen) + usize::from(screen.size().0)
+}
+
+/// History position of a cell in the current view.
+pub fn view_to_history(screen: &mut Screen, row: u16, col: u16) -> CellPos {
+    let offset = screen.scrollback();
+    CellPos {
+        row: scrollback_len(screen) - offset + usize::from(row),
+        col,
+    }
+}
+
+/// View row showing history row `row`, if it is on screen.
+pub fn history_to_view(screen: &mut Screen, row: usize) -> Option<u16> {
+    let top = scrollback_len(screen) - screen.scrollback();
+    let rows = usize::from(screen.size().0);
+    (row >= top && row < top + rows).then(|| (row - top) as u16)
+}
+
+/// Scrolls just enough to bring history row `row` into view.
+pub fn scroll_into_view(screen: &mut Screen, row: usize) {
+    let len = scrollback_len(screen);
+    let rows = usize::from(screen.size().0);
+    let top = len - screen.scrollback();
+    if row < top {
+        screen.set_scrollback(len - row);
+    } else if row >= top + rows {
+        screen.set_scrollback((len + rows).saturating_sub(row + 1));
+    }
+}
+
+/// Text of the selection, one line per row with trailing blanks and blank edge rows trimmed.
+/// The view's scroll position is left unchanged.
+pub fn selected_text(screen: &mut Screen, selection: &Selection) -> String {
+    let offset = screen.scrollback();
+    let len = scrollback_len(screen);
+    let cols = screen.size().1;
+    let (start, end) = selection.bounds();
+
+    let mut lines = Vec::new();
+    for row in start.row..=end.row {
+        let Some((from, to)) = selection.columns_on(row, cols) else {
+            lines.push(String::new());
+            continue;
+        };
+        // Put the row at the top of the view (or find it on the live screen) and read it back.
+        let (scroll, view_row) = if row < len {
+            (len - row, 0)
+        } else {
+            (0, row - len)
+        };
+        screen.set_scrollback(scroll);
+        let line = screen
+            .rows(from, to - from)
+            .nth(view_row)
+            .unwrap_or_default();
+        lines.push(line.trim_end().to_string());
+    }
+    screen.set_scrollback(offset);
+
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    let blank = lines.iter().take_while(|line| line.is_empty()).count();
+    lines[blank..].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use vt100::Parser;
+
+    use super::*;
+
+    /// 5x20 screen with `count` numbered lines written to it.
+    fn parser_with_lines(count: usize) -> Parser {
+        let mut parser = Parser::new(5, 20, 100);
+        for i in 0..count {
+            parser.process(format!("line {i}\r\n").as_bytes());
+        }
+        parser
+    }
+
+    fn pos(row: usize, col: u16) -> CellPos {
+        CellPos { row, col }
+    }
+
+    #[test]
+    fn view_and_history_positions_round_trip() {
+        let mut parser = parser_with_lines(30);
+        // 30 lines plus the empty prompt row: 26 rows went into scrollback.
+        assert_eq!(scrollback_len(parser.screen_mut()), 26);
+        assert_eq!(view_to_history(parser.screen_mut(), 0, 3), pos(26, 3));
+
+        parser.screen_mut().set_scrollback(10);
+        assert_eq!(view_to_history(parser.screen_mut(), 2, 0), pos(18, 0));
+        assert_eq!(history_to_view(parser.screen_mut(), 18), Some(2));
+        assert_eq!(history_to_view(parser.screen_mut(), 30), None);
+        assert_eq!(parser.screen().scrollback(), 10);
+    }
+
+    #[test]
+    fn extracts_text_across_scrollback_and_screen() {
+        let mut parser = parser_with_lines(30);
+        parser.screen_mut().set_scrollback(3);
+        let selection = Selection {
+            anchor: Some(pos(27, 10)),
+            cursor: pos(3, 0),
+        };
+        let text = selected_text(parser.screen_mut(), &selection);
+        let expected = (3..=27).map(|i| format!("line {i}")).collect::<Vec<_>>();
+        assert_eq!(text, expected.join("\n"));
+        // Reading does not move the view.
+        assert_eq!(parser.screen().scrollback(), 3);
+    }
+
+    #[test]
+    fn partial_first_and_last_rows() {
+        let mut parser = parser_with_lines(3);
+        let selection = Selection {
+            anchor: Some(pos(0, 5)),
+            cursor: pos(2, 3),
+        };
+        assert_eq!(
+            selected_text(parser.screen_mut(), &selection),
+            "0\nline 1\nline"
+        );
+        assert_eq!(selection.columns_on(1, 20), Some((0, 20)));
+        assert_eq!(selection.columns_on(3, 20), None);
+    }
+
+    #[test]
+    fn scroll_into_view_moves_minimally() {
+        let mut parser = parser_with_lines(30);
+        scroll_into_view(parser.screen_mut(), 20);
+        assert_eq!(history_to_view(parser.screen_mut(), 20), Some(0));
+        scroll_into_view(parser.screen_mut(), 26);
+        assert_eq!(history_to_view(parser.screen_mut(), 26), Some(4));
+        scroll_into_view(parser.screen_mut(), 30);
+        assert_eq!(parser.screen().scrollback(), 0);
+    }
+}
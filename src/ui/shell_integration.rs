@@ -0,0 +1,341 @@
+use std::{fs, path::Path};
+
+use portable_pty::CommandBuilder;
+use tempfile::TempDir;
+use vt100::Parser;
+
+use crate::i18n::tr;
+
+/// Longest OSC sequence we are willing to buffer across reads before giving up on it.
+const MAX_PENDING: usize = 8 * 1024;
+
+// The DEBUG trap fires before every simple command; `__bb_ready` runs last in
+// PROMPT_COMMAND so only the first command typed at the prompt is reported. Reading
+// `$BASH_COMMAND` there works with HISTCONTROL, unlike `history 1`, but names only the
+// first command of a pipeline or list. A DEBUG trap from the user's bashrc is replaced.
+const BASH_RC: &str = r#"[ -f ~/.bashrc ] && source ~/.bashrc
+__bb_preexec() {
+    [ -n "$__bb_at_prompt" ] || return
+    __bb_at_prompt=
+    # An empty line runs nothing before PROMPT_COMMAND.
+    [ "$1" = __bb_precmd ] && return
+    printf '\e]633;E;%s\a\e]133;C\a' "$1"
+}
+__bb_precmd() {
+    local status=$?
+    printf '\e]133;D;%s\a\e]133;A\a' "$status"
+    return $status
+}
+__bb_ready() { __bb_at_prompt=1; }
+PROMPT_COMMAND="__bb_precmd${PROMPT_COMMAND:+;$PROMPT_COMMAND};__bb_ready"
+trap '__bb_preexec "$BASH_COMMAND"' DEBUG
+PS1="$PS1"'\[\e]133;B\a\]'
+"#;
+
+const ZSH_ENV: &str = r#"[ -f "${BB_USER_ZDOTDIR:-$HOME}/.zshenv" ] && source "${BB_USER_ZDOTDIR:-$HOME}/.zshenv"
+"#;
+
+const ZSH_RC: &str = r#"[ -f "${BB_USER_ZDOTDIR:-$HOME}/.zshrc" ] && source "${BB_USER_ZDOTDIR:-$HOME}/.zshrc"
+ZDOTDIR="${BB_USER_ZDOTDIR:-$HOME}"
+__bb_preexec() { printf '\e]633;E;%s\a\e]133;C\a' "$1" }
+__bb_precmd() { printf '\e]133;D;%s\a\e]133;A\a' "$?" }
+autoload -Uz add-zsh-hook
+add-zsh-hook preexec __bb_preexec
+precmd_functions=(__bb_precmd $precmd_functions)
+PS1="$PS1"$'%{\e]133;B\a%}'
+"#;
+
+/// Rewrites the shell command so the spawned shell reports command boundaries with
+/// OSC 133 (`C` = output starts, `D;<status>` = finished) and the command line with
+/// OSC 633;E. The user's own rc files are sourced first.
+///
+/// The scripts live in a private temporary directory; keep the returned handle for as
+/// long as the shell runs. Dropping it removes the directory.
+pub fn inject(cmd: &mut CommandBuilder, shell: &str) -> Result<TempDir, String> {
+    let name = Path::new(shell)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(shell);
+    let scripts = tempfile::Builder::new()
+        .prefix("bbiribarabu-shell-")
+        .tempdir()
+        .map_err(|e| tr!("shell.script_failed", error = e))?;
+    let dir = scripts.path();
+    let write = |file: &str, body: &str| {
+        fs::write(dir.join(file), body).map_err(|e| tr!("shell.script_failed", error = e))
+    };
+
+    match name {
+        "bash" => {
+            write("bashrc", BASH_RC)?;
+            cmd.arg("--rcfile");
+            cmd.arg(dir.join("bashrc"));
+        }
+        "zsh" => {
+            write(".zshenv", ZSH_ENV)?;
+            write(".zshrc", ZSH_RC)?;
+            if let Some(user_dir) = std::env::var_os("ZDOTDIR") {
+                cmd.env("BB_USER_ZDOTDIR", user_dir);
+            }
+            cmd.env("ZDOTDIR", dir);
+        }
+        _ => return Err(tr!("shell.unsupported", shell = name)),
+    }
+    Ok(scripts)
+}
+
+/// A finished shell command as reported by the integration markers.
+#[derive(Debug, Clone)]
+pub struct CommandRecord {
+    pub command: String,
+    pub exit_code: Option<i32>,
+    /// Last lines of the command output, as rendered by a terminal.
+    pub output: Vec<String>,
+}
+
+impl CommandRecord {
+    pub fn failed(&self) -> bool {
+        self.exit_code.is_some_and(|code| code != 0)
+    }
+
+    /// Note text: the command with its exit code, followed by the output tail.
+    pub fn to_note(&self) -> String {
+        let mut note = match self.exit_code {
+            Some(code) => format!("$ {} (exit {})", self.command, code),
+            None => format!("$ {}", self.command),
+        };
+        for line in &self.output {
+            note.push('\n');
+            note.push_str(line);
+        }
+        note
+    }
+}
+
+struct Running {
+    command: String,
+    // Renders the output the same way the terminal does; rows scrolled off the top
+    // are dropped, so what remains is the tail.
+    output: Parser,
+}
+
+/// Watches the raw PTY stream for shell integration markers.
+pub struct CommandTracker {
+    tail_lines: usize,
+    pending: Vec<u8>,
+    next_command: Option<String>,
+    running: Option<Running>,
+    last: Option<CommandRecord>,
+}
+
+impl CommandTracker {
+    pub fn new(tail_lines: usize) -> Self {
+        Self {
+            tail_lines: tail_lines.clamp(1, u16::MAX as usize - 1),
+            pending: Vec::new(),
+            next_command: None,
+            running: None,
+            last: None,
+        }
+    }
+
+    pub fn last(&self) -> Option<&CommandRecord> {
+        self.last.as_ref()
+    }
+
+    /// Feeds a chunk of shell output. Returns the command that finished in it, if any.
+    pub fn feed(&mut self, bytes: &[u8], cols: u16) -> Option<CommandRecord> {
+        let mut data = std::mem::take(&mut self.pending);
+        data.extend_from_slice(bytes);
+
+        let mut finished = None;
+        let mut start = 0;
+        while let Some(offset) = find_osc_start(&data[start..]) {
+            let osc = start + offset;
+            self.output(&data[start..osc]);
+            let Some((payload, end)) = parse_osc(&data[osc..]) else {
+                // Incomplete sequence: keep it for the next read unless it is absurdly long.
+                if data.len() - osc <= MAX_PENDING {
+                    self.pending = data[osc..].to_vec();
+                }
+                return finished;
+            };
+            if let Some(record) = self.handle(&String::from_utf8_lossy(payload), cols) {
+                finished = Some(record);
+            }
+            start = osc + end;
+        }
+        // A trailing ESC may be the start of the next OSC.
+        if data.last() == Some(&0x1b) {
+            self.output(&data[start..data.len() - 1]);
+            self.pending = vec![0x1b];
+        } else {
+            self.output(&data[start..]);
+        }
+        finished
+    }
+
+    fn output(&mut self, bytes: &[u8]) {
+        if let Some(running) = self.running.as_mut() {
+            running.output.process(bytes);
+        }
+    }
+
+    fn handle(&mut self, payload: &str, cols: u16) -> Option<CommandRecord> {
+        if let Some(command) = payload.strip_prefix("633;E;") {
+            // Both hooks send the command line before `C`; one sent just before `D` still counts.
+            let command = command.trim_end().to_string();
+            match self.running.as_mut() {
+                Some(running) => running.command = command,
+                None => self.next_command = Some(command),
+            }
+            return None;
+        }
+        let mut parts = payload.split(';');
+        if parts.next() != Some("133") {
+            return None;
+        }
+        match parts.next() {
+            Some("C") => {
+                self.running = Some(Running {
+                    command: self.next_command.take().unwrap_or_default(),
+                    // One spare row for the line the cursor sits on after the final newline.
+                    output: Parser::new(self.tail_lines as u16 + 1, cols.max(1), 0),
+                });
+                None
+            }
+            Some("D") => {
+                let running = self.running.take()?;
+                let screen = running.output.screen();
+                let mut output = screen
+                    .rows(0, screen.size().1)
+                    .map(|row| row.trim_end().to_string())
+                    .collect::<Vec<_>>();
+                while output.last().is_some_and(|line| line.is_empty()) {
+                    output.pop();
+                }
+                let skip = output.len().saturating_sub(self.tail_lines);
+                output.drain(..skip);
+                while output.first().is_some_and(|line| line.is_empty()) {
+                    output.remove(0);
+                }
+                let record = CommandRecord {
+                    command: running.command,
+                    exit_code: parts.next().and_then(|code| code.parse().ok()),
+                    output,
+                };
+                self.last = Some(record.clone());
+                Some(record)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn find_osc_start(data: &[u8]) -> Option<usize> {
+    data.windows(2).position(|pair| pair == b"\x1b]")
+}
+
+/// Parses `ESC ] payload (BEL | ESC \)` at the start of `data`.
+/// Returns the payload and the length of the whole sequence.
+fn parse_osc(data: &[u8]) -> Option<(&[u8], usize)> {
+    let body = &data[2..];
+    for (idx, byte) in body.iter().enumerate() {
+        match byte {
+            0x07 => return Some((&body[..idx], idx + 3)),
+            0x1b if body.get(idx + 1) == Some(&b'\\') => return Some((&body[..idx], idx + 4)),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(tracker: &mut CommandTracker, chunks: &[&[u8]]) -> Option<CommandRecord> {
+        let mut finished = None;
+        for chunk in chunks {
+            if let Some(record) = tracker.feed(chunk, 40) {
+                finished = Some(record);
+            }
+        }
+        finished
+    }
+
+    #[test]
+    fn records_command_exit_code_and_output() {
+        let mut tracker = CommandTracker::new(20);
+        let record = run(
+            &mut tracker,
+            &[
+                b"$ \x1b]133;B\x07cargo test\r\n\x1b]633;E;cargo test\x07\x1b]133;C\x07",
+                b"running 2 tests\r\nerror: boom\r\n\x1b]133;D;101\x07\x1b]133;A\x07$ ",
+            ],
+        )
+        .unwrap();
+        assert_eq!(record.command, "cargo test");
+        assert_eq!(record.exit_code, Some(101));
+        assert!(record.failed());
+        assert_eq!(record.output, ["running 2 tests", "error: boom"]);
+        assert_eq!(
+            record.to_note(),
+            "$ cargo test (exit 101)\nrunning 2 tests\nerror: boom"
+        );
+    }
+
+    #[test]
+    fn markers_split_across_reads() {
+        let mut tracker = CommandTracker::new(20);
+        let record = run(
+            &mut tracker,
+            &[
+                b"\x1b]633;E;ls\x1b",
+                b"\\\x1b]13",
+                b"3;C\x07ok\r\n\x1b",
+                b"]133;D;0\x1b\\",
+            ],
+        )
+        .unwrap();
+        assert_eq!(record.command, "ls");
+        assert_eq!(record.exit_code, Some(0));
+        assert!(!record.failed());
+        assert_eq!(record.output, ["ok"]);
+    }
+
+    #[test]
+    fn bash_reports_command_after_output() {
+        let mut tracker = CommandTracker::new(20);
+        let record = run(
+            &mut tracker,
+            &[
+                b"\x1b]633;E;make\x07\x1b]133;D;0\x07\x1b]133;A\x07$ \x1b]133;B\x07false\r\n",
+                b"\x1b]133;C\x07\x1b]633;E;false\x07\x1b]133;D;1\x07\x1b]133;A\x07$ ",
+            ],
+        )
+        .unwrap();
+        assert_eq!(record.command, "false");
+        assert_eq!(record.exit_code, Some(1));
+        assert!(record.output.is_empty());
+        assert_eq!(record.to_note(), "$ false (exit 1)");
+    }
+
+    #[test]
+    fn keeps_only_output_tail() {
+        let mut tracker = CommandTracker::new(2);
+        let record = run(
+            &mut tracker,
+            &[b"\x1b]133;C\x07one\r\ntwo\r\nthree\r\n\x1b]133;D;1\x07"],
+        )
+        .unwrap();
+        assert_eq!(record.output, ["two", "three"]);
+    }
+
+    #[test]
+    fn prompt_without_command_is_ignored() {
+        let mut tracker = CommandTracker::new(20);
+        assert!(run(&mut tracker, &[b"\x1b]133;D;0\x07\x1b]133;A\x07$ "]).is_none());
+        assert!(tracker.last().is_none());
+    }
+}
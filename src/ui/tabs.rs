@@ -0,0 +1,190 @@
+use std::path::PathBuf;
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::config::ShellConfig;
+use crate::ui::pty_terminal::{PtyTerminal, ShellLaunch};
+use crate::ui::shell_integration::CommandRecord;
+
+/// Longest tab label shown in the terminal panel title.
+const LABEL_WIDTH: usize = 20;
+
+/// Something that happened in a tab during a poll.
+pub enum TabEvent {
+    /// A command finished (shell integration only).
+    CommandFinished(usize, CommandRecord),
+    /// The tab's shell exited.
+    Exited(usize),
+    /// The full scrollback dropped rows, so history row numbers in the tab moved.
+    HistoryTrimmed(usize),
+}
+
+/// Shells open in the terminal panel. There is always at least one; the active tab gets
+/// the keys and is drawn, the others keep reading their output in the background.
+pub struct TerminalTabs {
+    tabs: Vec<PtyTerminal>,
+    active: usize,
+    repo_root: PathBuf,
+    shell: ShellConfig,
+    launch: ShellLaunch,
+}
+
+impl TerminalTabs {
+    pub fn new(
+        repo_root: PathBuf,
+        rows: u16,
+        cols: u16,
+        shell: &ShellConfig,
+        launch: ShellLaunch,
+    ) -> Result<Self, String> {
+        let first = PtyTerminal::spawn(repo_root.clone(), rows, cols, shell, &launch)?;
+        Ok(Self {
+            tabs: vec![first],
+            active: 0,
+            repo_root,
+            shell: shell.clone(),
+            launch,
+        })
+    }
+
+    /// Changes a variable for shells started from now on; running ones keep theirs.
+    pub fn set_env(&mut self, name: &str, value: &str) {
+        self.launch.set_env(name, value);
+    }
+
+    pub fn active(&self) -> &PtyTerminal {
+        &self.tabs[self.active]
+    }
+
+    pub fn active_mut(&mut self) -> &mut PtyTerminal {
+        &mut self.tabs[self.active]
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Starts another shell in the repo root, sized like the current one, and switches to it.
+    pub fn open(&mut self) -> Result<(), String> {
+        let (rows, cols) = self.active().size();
+        let tab = PtyTerminal::spawn(
+            self.repo_root.clone(),
+            rows,
+            cols,
+            &self.shell,
+            &self.launch,
+        )?;
+        self.tabs.push(tab);
+        self.active = self.tabs.len() - 1;
+        Ok(())
+    }
+
+    /// Replaces the active tab's exited shell with a new one, in `cwd` or else the repo root.
+    /// A recording in progress carries on in the new shell.
+    pub fn respawn_active(&mut self, cwd: Option<PathBuf>) -> Result<(), String> {
+        let (rows, cols) = self.active().size();
+        let cwd = cwd
+            .filter(|dir| dir.is_dir())
+            .unwrap_or_else(|| self.repo_root.clone());
+        let mut tab = PtyTerminal::spawn(cwd, rows, cols, &self.shell, &self.launch)?;
+        if let Some(recording) = self.active_mut().take_recording() {
+            tab.continue_recording(recording);
+        }
+        self.tabs[self.active] = tab;
+        Ok(())
+    }
+
+    /// Closes the active tab and its shell. The last tab stays open; returns whether one closed.
+    pub fn close_active(&mut self) -> bool {
+        if self.tabs.len() == 1 {
+            return false;
+        }
+        self.tabs.remove(self.active);
+        self.active = self.active.min(self.tabs.len() - 1);
+        true
+    }
+
+    pub fn next(&mut self) {
+        self.active = (self.active + 1) % self.tabs.len();
+    }
+
+    pub fn prev(&mut self) {
+        self.active = (self.active + self.tabs.len() - 1) % self.tabs.len();
+    }
+
+    /// All tabs share the panel, so they all follow its size.
+    pub fn ensure_size(&mut self, rows: u16, cols: u16) {
+        for tab in &mut self.tabs {
+            tab.ensure_size(rows, cols);
+        }
+    }
+
+    /// Feeds pending output to every tab and reports finished commands and exited shells.
+    pub fn poll_output(&mut self) -> Vec<TabEvent> {
+        let mut events = Vec::new();
+        for (index, tab) in self.tabs.iter_mut().enumerate() {
+            let was_running = tab.exit_status().is_none();
+            if let Some(record) = tab.poll_output() {
+                events.push(TabEvent::CommandFinished(index, record));
+            }
+            if was_running && tab.exit_status().is_some() {
+                events.push(TabEvent::Exited(index));
+            }
+            if tab.take_history_trimmed() {
+                events.push(TabEvent::HistoryTrimmed(index));
+            }
+        }
+        events
+    }
+
+    /// Ends every recording in progress, e.g. before quitting.
+    pub fn stop_recordings(&mut self) -> Vec<Result<PathBuf, String>> {
+        self.tabs
+            .iter_mut()
+            .filter_map(PtyTerminal::stop_recording)
+            .collect()
+    }
+
+    /// One label per tab: its number, a dot while recording and the window title the shell
+    /// set, if any.
+    pub fn labels(&self) -> Vec<String> {
+        self.tabs
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| {
+                let mut label = format!("{}", index + 1);
+                if tab.is_recording() {
+                    label.push('●');
+                }
+                let title = tab.title().trim();
+                if !title.is_empty() {
+                    label.push(':');
+                    label.push_str(&truncate(title, LABEL_WIDTH));
+                }
+                label
+            })
+            .collect()
+    }
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut used = 0;
+    let mut out = String::new();
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > width.saturating_sub(1) {
+            out.push('…');
+            return out;
+        }
+        used += w;
+        out.push(c);
+    }
+    out
+}
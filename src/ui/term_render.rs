@@ -0,0 +1,177 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+use vt100::{Cell, Screen};
+
+/// Draws the visible vt100 screen cell by cell, so every glyph lands on the column the
+/// shell put it in. Nothing is re-wrapped: wide glyphs take their two cells, combining
+/// marks stay on their base cell, and the PTY cursor lines up with the text.
+pub struct TerminalView<'a> {
+    screen: &'a Screen,
+}
+
+impl<'a> TerminalView<'a> {
+    pub fn new(screen: &'a Screen) -> Self {
+        Self { screen }
+    }
+}
+
+impl Widget for TerminalView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (rows, cols) = self.screen.size();
+        for row in 0..rows.min(area.height) {
+            let y = area.y + row;
+            for col in 0..cols.min(area.width) {
+                let Some(cell) = self.screen.cell(row, col) else {
+                    break;
+                };
+                // The left half of a wide glyph already covers this column.
+                if cell.is_wide_continuation() {
+                    continue;
+                }
+                let x = area.x + col;
+                let style = cell_style(cell);
+                let wide = cell.is_wide();
+                if wide && col + 1 >= area.width {
+                    // Half a glyph does not fit; leave the cell blank rather than overflow.
+                    buf.get_mut(x, y).set_symbol(" ").set_style(style);
+                    continue;
+                }
+                let contents = cell.contents();
+                let symbol = if contents.is_empty() { " " } else { contents };
+                buf.get_mut(x, y).set_symbol(symbol).set_style(style);
+                if wide {
+                    // Hidden behind the wide glyph, like `Buffer::set_string` does it.
+                    buf.get_mut(x + 1, y).reset();
+                    buf.get_mut(x + 1, y).set_style(style);
+                }
+            }
+        }
+    }
+}
+
+fn cell_style(cell: &Cell) -> Style {
+    let mut style = Style::default()
+        .fg(color(cell.fgcolor()))
+        .bg(color(cell.bgcolor()));
+    if cell.bold() {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    if cell.italic() {
+        style = style.add_modifier(Modifier::ITALIC);
+    }
+    if cell.underline() {
+        style = style.add_modifier(Modifier::UNDERLINED);
+    }
+    if cell.inverse() {
+        style = style.add_modifier(Modifier::REVERSED);
+    }
+    style
+}
+
+fn color(color: vt100::Color) -> Color {
+    match color {
+        vt100::Color::Default => Color::Reset,
+        vt100::Color::Idx(idx) => Color::Indexed(idx),
+        vt100::Color::Rgb(r, g, b) => Color::Rgb(r, g, b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vt100::Parser;
+
+    use super::*;
+
+    fn render(input: &[u8], width: u16, height: u16) -> (Buffer, Parser) {
+        let mut parser = Parser::new(height, width, 0);
+        parser.process(input);
+        let area = Rect::new(0, 0, width, height);
+        let mut buffer = Buffer::empty(area);
+        TerminalView::new(parser.screen()).render(area, &mut buffer);
+        (buffer, parser)
+    }
+
+    fn plain() -> Style {
+        Style::default().fg(Color::Reset).bg(Color::Reset)
+    }
+
+    fn expected(lines: Vec<&str>) -> Buffer {
+        let mut buffer = Buffer::with_lines(lines);
+        let area = buffer.area;
+        buffer.set_style(area, plain());
+        buffer
+    }
+
+    #[test]
+    fn coloured_buffer_snapshot() {
+        let (buffer, _) = render(
+            b"\x1b[31mred\x1b[0m \x1b[1;4mbold\x1b[0m\r\n\
+              \x1b[38;5;208m2\x1b[38;2;10;20;30mT\x1b[48;5;4mB\x1b[0m\x1b[3mi\x1b[7mv\x1b[0m",
+            10,
+            2,
+        );
+
+        let mut expected = expected(vec!["red bold  ", "2TBiv     "]);
+        expected.set_style(Rect::new(0, 0, 3, 1), plain().fg(Color::Indexed(1)));
+        expected.set_style(
+            Rect::new(4, 0, 4, 1),
+            plain().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+        );
+        expected.set_style(Rect::new(0, 1, 1, 1), plain().fg(Color::Indexed(208)));
+        expected.set_style(Rect::new(1, 1, 1, 1), plain().fg(Color::Rgb(10, 20, 30)));
+        expected.set_style(
+            Rect::new(2, 1, 1, 1),
+            plain().fg(Color::Rgb(10, 20, 30)).bg(Color::Indexed(4)),
+        );
+        expected.set_style(
+            Rect::new(3, 1, 1, 1),
+            plain().add_modifier(Modifier::ITALIC),
+        );
+        expected.set_style(
+            Rect::new(4, 1, 1, 1),
+            plain().add_modifier(Modifier::ITALIC | Modifier::REVERSED),
+        );
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn hangul_prompt_keeps_columns_and_cursor() {
+        let (buffer, parser) = render("~/코드 (기능/로그인) $ ".as_bytes(), 24, 2);
+        assert_eq!(
+            buffer,
+            expected(vec!["~/코드 (기능/로그인) $  ", "                        "])
+        );
+        // The cursor sits right after the prompt, on the cell the next glyph is drawn in.
+        assert_eq!(parser.screen().cursor_position(), (0, 23));
+        let (buffer, _) = render("~/코드 (기능/로그인) $ x".as_bytes(), 24, 2);
+        assert_eq!(buffer.get(23, 0).symbol(), "x");
+    }
+
+    #[test]
+    fn full_width_row_does_not_rewrap() {
+        // Exactly fills the row; the next line must stay on the next row.
+        let (buffer, _) = render("가나다라\r\nok".as_bytes(), 8, 2);
+        assert_eq!(buffer, expected(vec!["가나다라", "ok      "]));
+    }
+
+    #[test]
+    fn wide_glyph_wraps_like_the_terminal() {
+        // Only one column is left, so the terminal moves the glyph to the next row.
+        let (buffer, parser) = render("abc한".as_bytes(), 4, 2);
+        assert_eq!(buffer, expected(vec!["abc ", "한  "]));
+        assert_eq!(parser.screen().cursor_position(), (1, 2));
+    }
+
+    #[test]
+    fn emoji_and_combining_marks() {
+        let (buffer, parser) = render("🦀e\u{301}!".as_bytes(), 6, 1);
+        assert_eq!(buffer.get(0, 0).symbol(), "🦀");
+        assert_eq!(buffer.get(2, 0).symbol(), "e\u{301}");
+        assert_eq!(buffer.get(3, 0).symbol(), "!");
+        assert_eq!(parser.screen().cursor_position(), (0, 4));
+    }
+}
@@ -0,0 +1,203 @@
+use unicode_width::UnicodeWidthStr;
+use vt100::Screen;
+
+use crate::ui::selection;
+
+/// A hit in the terminal history: `row` counts from the oldest scrollback line like
+/// [`selection::CellPos`], `start..end` are screen columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub row: usize,
+    pub start: u16,
+    pub end: u16,
+}
+
+/// Every occurrence of `query` in the scrollback and on the screen, oldest first. Lower-case
+/// queries ignore case. Matches do not span rows. The view's scroll position is left unchanged.
+pub fn find_matches(screen: &mut Screen, query: &str) -> Vec<SearchMatch> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let ignore_case = !query.chars().any(char::is_uppercase);
+    let needle = query.chars().collect::<Vec<_>>();
+    let offset = screen.scrollback();
+    let len = selection::scrollback_len(screen);
+    let (rows, cols) = screen.size();
+
+    let mut matches = Vec::new();
+    for row in 0..len + usize::from(rows) {
+        // Put the row at the top of the view (or find it on the live screen) and read it back.
+        let (scroll, view_row) = if row < len {
+            (len - row, 0)
+        } else {
+            (0, (row - len) as u16)
+        };
+        screen.set_scrollback(scroll);
+        let chars = row_chars(screen, view_row, cols);
+        let mut at = 0;
+        while at + needle.len() <= chars.len() {
+            let hit = needle
+                .iter()
+                .zip(&chars[at..])
+                .all(|(&n, &(c, _, _))| same_char(n, c, ignore_case));
+            if hit {
+                let last = chars[at + needle.len() - 1];
+                matches.push(SearchMatch {
+                    row,
+                    start: chars[at].1,
+                    end: last.2,
+                });
+                at += needle.len();
+            } else {
+                at += 1;
+            }
+        }
+    }
+    screen.set_scrollback(offset);
+    matches
+}
+
+/// Characters of a view row with the columns of the cell each one is drawn in.
+fn row_chars(screen: &Screen, row: u16, cols: u16) -> Vec<(char, u16, u16)> {
+    let mut chars = Vec::new();
+    for col in 0..cols {
+        let Some(cell) = screen.cell(row, col) else {
+            break;
+        };
+        if cell.is_wide_continuation() {
+            continue;
+        }
+        let contents = cell.contents();
+        let end = col + contents.width().clamp(1, 2) as u16;
+        if contents.is_empty() {
+            chars.push((' ', col, end));
+        }
+        chars.extend(contents.chars().map(|c| (c, col, end)));
+    }
+    chars
+}
+
+fn same_char(a: char, b: char, ignore_case: bool) -> bool {
+    a == b || (ignore_case && a.to_lowercase().eq(b.to_lowercase()))
+}
+
+/// Incremental search over the terminal history.
+#[derive(Debug, Default)]
+pub struct TermSearch {
+    pub query: String,
+    pub matches: Vec<SearchMatch>,
+    /// Index into `matches` of the hit the view jumped to.
+    pub current: Option<usize>,
+}
+
+impl TermSearch {
+    /// Replaces the matches and picks the last one at or above history row `bottom`
+    /// (the most recent hit the user could have seen), or the oldest below it.
+    pub fn set_matches(&mut self, matches: Vec<SearchMatch>, bottom: usize) {
+        let above = matches.partition_point(|m| m.row <= bottom);
+        self.current = match above {
+            0 if matches.is_empty() => None,
+            0 => Some(0),
+            n => Some(n - 1),
+        };
+        self.matches = matches;
+    }
+
+    pub fn current_match(&self) -> Option<SearchMatch> {
+        self.current.map(|index| self.matches[index])
+    }
+
+    /// Moves to the previous hit (further back in the history), wrapping around.
+    pub fn older(&mut self) -> Option<SearchMatch> {
+        let len = self.matches.len();
+        self.current = self.current.map(|index| (index + len - 1) % len);
+        self.current_match()
+    }
+
+    /// Moves to the next hit (towards the live screen), wrapping around.
+    pub fn newer(&mut self) -> Option<SearchMatch> {
+        let len = self.matches.len();
+        self.current = self.current.map(|index| (index + 1) % len);
+        self.current_match()
+    }
+
+    /// Hits on history rows `top..top + rows`.
+    pub fn matches_in(&self, top: usize, rows: usize) -> &[SearchMatch] {
+        let from = self.matches.partition_point(|m| m.row < top);
+        let to = self.matches.partition_point(|m| m.row < top + rows);
+        &self.matches[from..to]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vt100::Parser;
+
+    use super::*;
+
+    fn parser_with(text: &str) -> Parser {
+        let mut parser = Parser::new(5, 20, 100);
+        parser.process(text.as_bytes());
+        parser
+    }
+
+    fn hit(row: usize, start: u16, end: u16) -> SearchMatch {
+        SearchMatch { row, start, end }
+    }
+
+    #[test]
+    fn finds_hits_in_scrollback_and_on_screen() {
+        let mut text = String::new();
+        for i in 0..30 {
+            text.push_str(&format!("line {i}\r\n"));
+        }
+        let mut parser = parser_with(&text);
+        parser.screen_mut().set_scrollback(4);
+        let matches = find_matches(parser.screen_mut(), "line 2");
+        let rows = matches.iter().map(|m| m.row).collect::<Vec<_>>();
+        assert_eq!(rows, [2, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29]);
+        assert_eq!(matches[0], hit(2, 0, 6));
+        // Searching does not move the view.
+        assert_eq!(parser.screen().scrollback(), 4);
+    }
+
+    #[test]
+    fn case_and_repeated_hits() {
+        let mut parser = parser_with("Error: error ERROR\r\n");
+        assert_eq!(
+            find_matches(parser.screen_mut(), "error"),
+            [hit(0, 0, 5), hit(0, 7, 12), hit(0, 13, 18)]
+        );
+        assert_eq!(find_matches(parser.screen_mut(), "ERROR"), [hit(0, 13, 18)]);
+        assert_eq!(find_matches(parser.screen_mut(), "aa").len(), 0);
+        let mut parser = parser_with("aaaa");
+        assert_eq!(
+            find_matches(parser.screen_mut(), "aa"),
+            [hit(0, 0, 2), hit(0, 2, 4)]
+        );
+    }
+
+    #[test]
+    fn wide_glyphs_map_to_screen_columns() {
+        let mut parser = parser_with("빌드 실패: 테스트\r\n");
+        assert_eq!(find_matches(parser.screen_mut(), "실패"), [hit(0, 5, 9)]);
+        assert_eq!(find_matches(parser.screen_mut(), "패: 테"), [hit(0, 7, 13)]);
+    }
+
+    #[test]
+    fn navigation_wraps_and_starts_near_the_bottom() {
+        let matches = vec![hit(1, 0, 1), hit(5, 0, 1), hit(9, 0, 1)];
+        let mut search = TermSearch::default();
+        search.set_matches(matches.clone(), 6);
+        assert_eq!(search.current_match(), Some(hit(5, 0, 1)));
+        assert_eq!(search.newer(), Some(hit(9, 0, 1)));
+        assert_eq!(search.newer(), Some(hit(1, 0, 1)));
+        assert_eq!(search.older(), Some(hit(9, 0, 1)));
+        assert_eq!(search.matches_in(4, 6), &matches[1..3]);
+
+        search.set_matches(matches, 0);
+        assert_eq!(search.current, Some(0));
+        search.set_matches(Vec::new(), 0);
+        assert_eq!(search.older(), None);
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     io::{self, Stdout},
+    ops::Range,
     path::PathBuf,
     sync::{
         Arc,
@@ -10,26 +11,48 @@ use std::{
 };
 
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers, MouseEventKind},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    terminal::{
+        self, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+    },
 };
+use portable_pty::ExitStatus;
 use ratatui::{
-    Terminal,
+    Frame, Terminal,
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Modifier, Style},
+    layout::Rect,
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
 };
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::{
     app::AppState,
-    ui::pty_terminal::{PtyTerminal, encode_key_event},
+    config::{ConfigLayers, ConfigScope, ConfigSource, ShellConfig},
+    i18n::tr,
+    log::model::LogItem,
+    log::recap::Recap,
+    ui::keymap::{KeyMap, Route},
+    ui::layout::{LayoutInfo, Panel, PanelLayout},
+    ui::pty_terminal::{PtyTerminal, ShellLaunch},
+    ui::selection::Selection,
+    ui::tabs::{TabEvent, TerminalTabs},
+    ui::term_render::TerminalView,
+    ui::term_search::TermSearch,
+    ui::watch::{FileWatcher, WatchEvent},
     voice,
 };
 
+// 브랜치 삭제는 HEAD를 건드리지 않아서 주기적으로 다시 센다
+const ORPHAN_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+// 새로 띄우는 쉘에 넘기는 현재 브랜치 (이미 떠 있는 쉘은 시작할 때 값 그대로)
+const BRANCH_ENV: &str = "BBIRIBARABU_BRANCH";
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 enum Focus {
     Terminal,
@@ -54,7 +77,11 @@ struct UiState {
     mode: InputMode,
     log_input: String,
     input_cursor: usize,
-    pty: PtyTerminal,
+    // 터미널 탭 (항상 하나 이상)
+    terms: TerminalTabs,
+    keys: KeyMap,
+    // 패널 배치 (설정에서 읽고 종료할 때 바뀌었으면 저장)
+    layout: PanelLayout,
     debug_overlay: bool,
     status_message: Option<(String, Instant, Duration)>,
     voice_task: Option<mpsc::Receiver<VoiceEvent>>,
@@ -68,16 +95,120 @@ struct UiState {
     search_query: String,
     search_cursor: usize,
     search_scroll_x: usize,
+    log_view: LogView,
+    // 로그 디렉터리/.git/HEAD 감시 (시작 못 하면 None, 주기적 확인으로 대체)
+    watcher: Option<FileWatcher>,
+    // 감시를 시작하지 못한 로그 디렉터리 (매 프레임 다시 시도하지 않고 주기적 확인으로 대체)
+    watcher_failed_for: Option<PathBuf>,
+    // 로그 파일이 바뀌어서 저장소 스냅샷을 다시 확인해야 함
+    logs_dirty: bool,
+    // 브랜치가 사라진 로그북 수 (헤더에 `gc` 안내 표시)
+    orphans: usize,
+    orphans_checked: Option<Instant>,
+    // 브랜치 전환 직후 띄우는 요약 창 (아무 키나 누르면 닫힘)
+    recap: Option<Recap>,
+    // 터미널 출력 선택 중 (마우스 드래그 또는 선택 모드 키)
+    selection: Option<Selection>,
+    // 선택을 마치고 설명을 입력받는 중인 코드 블록
+    pending_snippet: Option<String>,
+    // 녹화를 끝내고 설명을 입력받는 중인 녹화 파일
+    pending_cast: Option<PathBuf>,
+    // 터미널 스크롤백 검색 중
+    term_search: Option<TermSearch>,
+    // 리더 키를 누르고 다음 키를 기다리는 중
+    leader_pending: bool,
+}
+
+/// 로그 목록 표시용 캐시. 브랜치/검색어/저장소 스냅샷이 바뀔 때만 다시 만든다.
+#[derive(Default)]
+struct LogView {
+    branch: String,
+    query: String,
+    source: Arc<Vec<LogItem>>,
+    // 화면 순서 (검색 중에는 관련도순, 아니면 최신순)
+    items: Arc<Vec<LogItem>>,
+    // 표시 문자열과 강조할 byte 범위
+    lines: Arc<Vec<(String, Vec<Range<usize>>)>>,
+}
+
+impl LogView {
+    fn is_current(&self, branch: &str, query: &str, source: &Arc<Vec<LogItem>>) -> bool {
+        self.branch == branch && self.query == query && Arc::ptr_eq(&self.source, source)
+    }
+
+    fn build(app: &AppState, branch: &str, query: &str, source: Arc<Vec<LogItem>>) -> Self {
+        let (items, highlights) = if query.is_empty() {
+            let items = source.iter().rev().cloned().collect::<Vec<_>>();
+            let highlights = vec![Vec::new(); items.len()];
+            (items, highlights)
+        } else {
+            app.log_store
+                .search(branch, query)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|hit| (hit.item, hit.highlights))
+                .unzip::<_, _, Vec<_>, Vec<_>>()
+        };
+        let lines = items
+            .iter()
+            .zip(highlights)
+            .map(|(it, ranges)| {
+                let prefix = format!("[{}] ", it.created_at.format("%m-%d %H:%M"));
+                let ranges = ranges
+                    .into_iter()
+                    .map(|r| r.start + prefix.len()..r.end + prefix.len())
+                    .collect::<Vec<_>>();
+                // 여러 줄 로그는 한 줄로 보여 준다 (같은 길이로 바꿔서 강조 범위 유지)
+                let mut text = it.text.replace(['\n', '\r'], " ");
+                if let Some(snippet) = &it.snippet {
+                    text.push(' ');
+                    text.push_str(&tr!("tui.snippet_marker", lines = snippet.lines().count()));
+                }
+                if it.cast.is_some() {
+                    text.push(' ');
+                    text.push_str(&tr!("tui.cast_marker"));
+                }
+                (format!("{}{}", prefix, text), ranges)
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            branch: branch.to_string(),
+            query: query.to_string(),
+            source,
+            items: Arc::new(items),
+            lines: Arc::new(lines),
+        }
+    }
 }
 
 impl UiState {
-    fn new(repo_root: PathBuf, rows: u16, cols: u16) -> Result<Self, String> {
+    fn new(
+        repo_root: PathBuf,
+        area: Rect,
+        keys: KeyMap,
+        layout: PanelLayout,
+        shell: &ShellConfig,
+        launch: ShellLaunch,
+    ) -> Result<Self, String> {
+        let focus = if layout.shows_terminal(Panel::Terminal) {
+            Focus::Terminal
+        } else {
+            Focus::LogInput
+        };
+        // 터미널 패널이 숨겨진 배치면 기본 크기로 띄우고 패널이 보일 때 맞춘다
+        let (rows, cols) = layout
+            .compute(area, Panel::Terminal)
+            .pty_size()
+            .unwrap_or((24, 80));
         Ok(Self {
-            focus: Focus::Terminal,
+            focus,
             mode: InputMode::Normal,
             log_input: String::new(),
             input_cursor: 0,
-            pty: PtyTerminal::spawn(repo_root, rows, cols)?,
+            terms: TerminalTabs::new(repo_root, rows, cols, shell, launch)?,
+            keys,
+            layout,
             debug_overlay: false,
             status_message: None,
             voice_task: None,
@@ -91,9 +222,42 @@ impl UiState {
             search_query: String::new(),
             search_cursor: 0,
             search_scroll_x: 0,
+            log_view: LogView::default(),
+            watcher: None,
+            watcher_failed_for: None,
+            logs_dirty: true,
+            orphans: 0,
+            orphans_checked: None,
+            recap: None,
+            selection: None,
+            pending_snippet: None,
+            pending_cast: None,
+            term_search: None,
+            leader_pending: false,
         })
     }
 
+    /// 저장소 디렉터리가 바뀌면(임시 로그북 ↔ repo 로그북) 감시 대상을 다시 잡는다
+    fn ensure_watcher(&mut self, app: &AppState) {
+        let log_dir = app.log_store.dir();
+        if self.watcher.as_ref().map(FileWatcher::log_dir) == Some(log_dir)
+            || self.watcher_failed_for.as_deref() == Some(log_dir)
+        {
+            return;
+        }
+        self.watcher = FileWatcher::start(log_dir, app.git.as_ref().map(|git| git.git_dir())).ok();
+        self.watcher_failed_for = self.watcher.is_none().then(|| log_dir.to_path_buf());
+        self.logs_dirty = true;
+    }
+
+    fn layout_info(&self, area: Rect) -> LayoutInfo {
+        let panel = match self.focus {
+            Focus::Terminal => Panel::Terminal,
+            Focus::LogInput => Panel::Logs,
+        };
+        self.layout.compute(area, panel)
+    }
+
     fn set_status(&mut self, message: impl Into<String>) {
         self.set_status_for(message, Duration::from_secs(2));
     }
@@ -104,30 +268,60 @@ impl UiState {
 }
 
 pub fn run(app: &mut AppState) -> io::Result<()> {
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    let keys = KeyMap::from_config(&app.config.keys).map_err(to_io_error)?;
+    let layout = PanelLayout::from_config(&app.config.ui).map_err(to_io_error)?;
+    let mut launch = ShellLaunch::from_config(&app.config.shell).map_err(to_io_error)?;
+    // 쉘 안의 스크립트가 지금 로그북을 찾을 수 있게
+    launch.set_env(BRANCH_ENV, &app.current_branch);
+    launch.set_env(
+        "BBIRIBARABU_LOG_DIR",
+        &app.log_store.dir().to_string_lossy(),
+    );
 
-    let size = terminal.size()?;
-    let layout = compute_layout(size);
+    // 쉘 실행이 실패해도 터미널이 raw 모드로 남지 않도록 화면을 바꾸기 전에 띄운다
+    let (cols, rows) = terminal::size()?;
     let mut ui_state = UiState::new(
         app.repo_root.clone(),
-        layout.term_inner.height,
-        layout.term_inner.width,
+        Rect::new(0, 0, cols, rows),
+        keys,
+        layout,
+        &app.config.shell,
+        launch,
     )
     .map_err(to_io_error)?;
 
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
     let res = run_loop(&mut terminal, app, &mut ui_state);
+    // 대체 화면을 나간 뒤에 보여 줄 저장 실패 메시지
+    let mut errors = save_recordings(app, &mut ui_state)
+        .into_iter()
+        .map(|e| tr!("tui.cast_failed", error = e))
+        .collect::<Vec<_>>();
+    if let Err(e) = save_layout(app, &ui_state.layout) {
+        errors.push(tr!("tui.layout_save_failed", error = e));
+    }
 
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
+        DisableBracketedPaste,
         DisableMouseCapture,
         LeaveAlternateScreen
     )?;
     terminal.show_cursor()?;
+    for error in errors {
+        eprintln!("{}", error);
+    }
 
     res
 }
@@ -139,8 +333,71 @@ fn run_loop(
 ) -> io::Result<()> {
     loop {
         let prev_branch = app.current_branch.clone();
-        app.refresh_branch_if_needed();
+        let prev_scratch = app.scratch;
+        ui.ensure_watcher(app);
+        match &ui.watcher {
+            Some(watcher) => {
+                let mut head_changed = false;
+                for event in watcher.drain() {
+                    match event {
+                        WatchEvent::Logs(path) => {
+                            app.log_store.forget_file(&path);
+                            ui.logs_dirty = true;
+                        }
+                        WatchEvent::Head => head_changed = true,
+                    }
+                }
+                if head_changed {
+                    app.refresh_branch();
+                }
+            }
+            None => {
+                app.refresh_branch_if_needed();
+                ui.logs_dirty = true;
+            }
+        }
+        if prev_branch != app.current_branch
+            || ui
+                .orphans_checked
+                .is_none_or(|at| at.elapsed() > ORPHAN_CHECK_INTERVAL)
+        {
+            ui.orphans = app.orphaned_logbooks().map_or(0, |orphans| orphans.len());
+            ui.orphans_checked = Some(Instant::now());
+        }
+        if prev_branch != app.current_branch {
+            ui.set_status(tr!("tui.branch_switched", branch = app.current_branch));
+            ui.terms.set_env(BRANCH_ENV, &app.current_branch);
+            ui.recap = None;
+            let notes = app.config.ui.recap_notes;
+            if notes > 0 && !app.scratch {
+                let items = app
+                    .log_store
+                    .snapshot(&app.current_branch)
+                    .unwrap_or_default();
+                let recap = Recap::build(&app.current_branch, &items, notes, chrono::Local::now());
+                // 처음 가 보는 브랜치면 보여 줄 것이 없다
+                if !recap.is_empty() {
+                    ui.recap = Some(recap);
+                }
+            }
+        }
         if prev_branch != app.current_branch && ui.mode == InputMode::EditingLog {
+            // 녹화는 다시 만들 수 없으니 설명을 받던 중이어도 녹화한 브랜치에 저장한다
+            if let Some(cast) = ui.pending_cast.take() {
+                let branch = if prev_scratch == app.scratch {
+                    &prev_branch
+                } else {
+                    &app.current_branch
+                };
+                let caption = match ui.log_input.trim() {
+                    "" => tr!("tui.cast_caption"),
+                    caption => caption.to_string(),
+                };
+                if let Err(e) = app.log_store.append_cast(branch, &caption, &cast) {
+                    ui.set_status(tr!("tui.cast_failed", error = e));
+                }
+            }
+            ui.pending_snippet = None;
             ui.mode = InputMode::Normal;
             ui.log_input.clear();
             ui.input_cursor = 0;
@@ -150,40 +407,82 @@ fn run_loop(
         if prev_branch != app.current_branch && ui.mode == InputMode::ConfirmDelete {
             ui.mode = InputMode::Normal;
         }
-        if let Some((_, at, duration)) = ui.status_message.as_ref() {
-            if at.elapsed() > *duration {
-                ui.status_message = None;
-            }
+        if let Some((_, at, duration)) = ui.status_message.as_ref()
+            && at.elapsed() > *duration
+        {
+            ui.status_message = None;
         }
 
-        let layout = compute_layout(terminal.size()?);
+        // 로그 패널만 보이는 배치에서 터미널로 가면 터미널을 확대해서 보여 준다
+        if ui.focus == Focus::Terminal {
+            ui.layout.reveal_terminal();
+        }
+        let layout = ui.layout_info(terminal.size()?);
         let input_inner_width = layout.input.width.saturating_sub(2) as usize;
-        ui.pty
-            .ensure_size(layout.term_inner.height, layout.term_inner.width);
-        ui.pty.poll_output();
-
-        let log_items_raw = app
-            .log_store
-            .list(&app.current_branch)
-            .unwrap_or_default()
-            .into_iter()
-            .rev()
-            .collect::<Vec<_>>();
+        if let Some((rows, cols)) = layout.pty_size() {
+            ui.terms.ensure_size(rows, cols);
+        }
+        // 실패한 명령은 로그로 남길지 제안만 한다 (다른 탭이면 탭 번호도 알려 준다)
+        for event in ui.terms.poll_output() {
+            match event {
+                TabEvent::CommandFinished(tab, record) if record.failed() => {
+                    let code = record.exit_code.unwrap_or_default();
+                    let message = if tab == ui.terms.active_index() {
+                        tr!(
+                            "tui.command_failed",
+                            command = record.command,
+                            code = code,
+                            key = ui.keys.in_terminal(ui.keys.log_command)
+                        )
+                    } else {
+                        tr!(
+                            "tui.command_failed_in_tab",
+                            command = record.command,
+                            code = code,
+                            tab = tab + 1
+                        )
+                    };
+                    ui.set_status_for(message, Duration::from_secs(10));
+                }
+                // 가득 찬 스크롤백에서 오래된 줄이 밀려나면 선택과 검색 결과의 행 번호가 어긋난다
+                TabEvent::HistoryTrimmed(tab) if tab == ui.terms.active_index() => {
+                    ui.selection = None;
+                    if let Some(search) = ui.term_search.as_mut() {
+                        let pty = ui.terms.active_mut();
+                        let (rows, _) = pty.size();
+                        let bottom = pty.view_to_history(rows.saturating_sub(1), 0).row;
+                        search.set_matches(pty.search(&search.query), bottom);
+                    }
+                }
+                // 현재 탭의 종료는 터미널 패널에 안내가 뜬다
+                TabEvent::Exited(tab) if tab != ui.terms.active_index() => {
+                    ui.set_status_for(
+                        tr!("tui.tab_exited", tab = tab + 1),
+                        Duration::from_secs(10),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        // 로그 파일 변경 알림이 오거나 브랜치/검색어가 바뀐 경우에만 저장소를 확인한다
         let query = ui.search_query.trim();
-        let query_lower = query.to_lowercase();
-        let log_items_filtered = if query.is_empty() {
-            log_items_raw.clone()
-        } else {
-            log_items_raw
-                .iter()
-                .filter(|it| it.text.to_lowercase().contains(&query_lower))
-                .cloned()
-                .collect::<Vec<_>>()
-        };
-        let log_items = log_items_filtered
-            .iter()
-            .map(|it| format!("[{}] {}", it.created_at.format("%m-%d %H:%M"), it.text))
-            .collect::<Vec<_>>();
+        if ui.logs_dirty || ui.log_view.branch != app.current_branch || ui.log_view.query != query {
+            ui.logs_dirty = false;
+            let snapshot = app
+                .log_store
+                .snapshot(&app.current_branch)
+                .unwrap_or_default();
+            if !ui
+                .log_view
+                .is_current(&app.current_branch, query, &snapshot)
+            {
+                let query = query.to_string();
+                ui.log_view = LogView::build(app, &app.current_branch, &query, snapshot);
+            }
+        }
+        let log_items_filtered = ui.log_view.items.clone();
+        let log_items = ui.log_view.lines.clone();
         let log_inner_height = layout.logs.height.saturating_sub(2) as usize;
         if log_items.is_empty() {
             ui.selected_log_index = 0;
@@ -210,9 +509,9 @@ fn run_loop(
         if let Some(rx) = ui.voice_task.as_ref() {
             match rx.try_recv() {
                 Ok(VoiceEvent::Status(msg)) => {
-                    if msg.contains("다운로드합니다") {
+                    if msg == tr!("model.downloading") {
                         ui.set_status_for(msg, Duration::from_secs(300));
-                    } else if msg.contains("다운로드 완료") {
+                    } else if msg == tr!("model.downloaded") {
                         ui.set_status_for(msg, Duration::from_secs(2));
                     } else {
                         ui.set_status(msg);
@@ -226,24 +525,25 @@ fn run_loop(
                         Ok(t) => {
                             let trimmed = t.trim();
                             if trimmed.is_empty() {
-                                ui.set_status("보이스 인식 결과 없음");
+                                ui.set_status(tr!("tui.voice_empty"));
                             } else if let Err(e) =
                                 app.log_store.append_text(&app.current_branch, trimmed)
                             {
-                                ui.set_status(format!("보이스 로그 실패: {}", e));
+                                ui.set_status(tr!("tui.voice_log_failed", error = e));
                             } else {
-                                ui.set_status("로그 저장되었습니다");
+                                ui.logs_dirty = true;
+                                ui.set_status(tr!("tui.saved"));
                             }
                         }
                         Err(e) => {
-                            if e == "녹음이 취소되었습니다" {
-                                ui.set_status("녹음 취소됨");
+                            if e == tr!("voice.cancelled") {
+                                ui.set_status(tr!("tui.recording_cancelled"));
                                 continue;
                             }
-                            if e.starts_with("모델 준비 실패:") {
+                            if e.starts_with(&tr!("voice.model_failed", error = "")) {
                                 ui.set_status_for(e, Duration::from_secs(6));
                             } else {
-                                ui.set_status(format!("보이스 인식 실패: {}", e));
+                                ui.set_status(tr!("voice.failed", error = e));
                             }
                         }
                     }
@@ -253,23 +553,75 @@ fn run_loop(
                     ui.voice_task = None;
                     ui.voice_signal = None;
                     ui.voice_stopping = false;
-                    ui.set_status("보이스 인식 실패");
+                    ui.set_status(tr!("tui.voice_failed"));
+                }
+            }
+        }
+
+        // 스크롤백 검색 결과를 화면 좌표로: (행, 시작 열, 끝 열, 현재 결과인지)
+        let mut search_cells = Vec::new();
+        if let Some(search) = &ui.term_search {
+            let (rows, _) = ui.terms.active().size();
+            let top = ui.terms.active_mut().view_to_history(0, 0).row;
+            let current = search.current_match();
+            for hit in search.matches_in(top, usize::from(rows)) {
+                let view_row = (hit.row - top) as u16;
+                search_cells.push((view_row, hit.start, hit.end, Some(*hit) == current));
+            }
+        }
+
+        // 선택 영역을 화면 좌표로: (행, 시작 열, 끝 열)
+        let (term_rows, term_cols) = ui.terms.active().size();
+        let mut selection_cells = Vec::new();
+        let mut selection_cursor = None;
+        if let Some(selection) = ui.selection {
+            for view_row in 0..term_rows {
+                let row = ui.terms.active_mut().view_to_history(view_row, 0).row;
+                if let Some((from, to)) = selection.columns_on(row, term_cols) {
+                    selection_cells.push((view_row, from, to));
                 }
             }
+            selection_cursor = ui
+                .terms
+                .active_mut()
+                .history_to_view(selection.cursor.row)
+                .map(|row| (row, selection.cursor.col));
         }
 
         terminal.draw(|f| {
-            let layout = compute_layout(f.size());
+            let layout = ui.layout_info(f.size());
             let mut final_cursor_abs: Option<(u16, u16)> = None;
 
-            let header = Paragraph::new(Line::from(vec![
-                Span::styled(" repo: ", Style::default().add_modifier(Modifier::BOLD)),
+            let root_label = if app.in_repo {
+                tr!("tui.header.repo")
+            } else {
+                tr!("tui.header.dir")
+            };
+            let branch_span = if app.scratch {
+                Span::styled(
+                    tr!("tui.header.scratch"),
+                    Style::default().add_modifier(Modifier::ITALIC),
+                )
+            } else {
+                Span::raw(&app.current_branch)
+            };
+            let mut header_spans = vec![
+                Span::styled(root_label, Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw(app.repo_root.display().to_string()),
                 Span::raw(" | "),
-                Span::styled("branch: ", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(&app.current_branch),
-            ]))
-            .block(
+                Span::styled(
+                    tr!("tui.header.branch"),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                branch_span,
+            ];
+            if ui.orphans > 0 {
+                header_spans.push(Span::styled(
+                    tr!("tui.header.orphans", count = ui.orphans),
+                    Style::default().add_modifier(Modifier::DIM),
+                ));
+            }
+            let header = Paragraph::new(Line::from(header_spans)).block(
                 Block::default()
                     .borders(Borders::ALL)
                     .title(" bbiribarabu "),
@@ -278,41 +630,105 @@ fn run_loop(
 
             // Terminal panel
             let term_area = layout.terminal;
-            let title = match ui.focus {
-                Focus::Terminal => " Terminal (focus) ",
-                Focus::LogInput => " Terminal ",
+            let title = match (&ui.term_search, ui.focus) {
+                _ if ui.selection.is_some() => tr!("tui.title.terminal_select"),
+                (Some(search), _) => term_search_title(search),
+                (None, Focus::Terminal) => match ui.keys.leader {
+                    Some(leader) => tr!("tui.title.terminal_leader", leader = leader),
+                    None => tr!("tui.title.terminal_focus"),
+                },
+                (None, Focus::LogInput) => tr!("tui.title.terminal"),
             };
-            let block = Block::default().borders(Borders::ALL).title(title);
+            // 탭이 여러 개면 제목 뒤에 탭 목록을 붙이고 현재 탭을 반전해서 보여 준다
+            let mut title_spans = vec![Span::raw(title)];
+            if ui.leader_pending
+                && let Some(leader) = ui.keys.leader
+            {
+                title_spans.push(Span::styled(
+                    tr!("tui.title.leader_pending", leader = leader),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+            if ui.terms.active().is_recording() {
+                title_spans.push(Span::styled(
+                    tr!("tui.title.recording"),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                ));
+            }
+            if ui.terms.len() > 1 {
+                for (index, label) in ui.terms.labels().into_iter().enumerate() {
+                    let style = if index == ui.terms.active_index() {
+                        Style::default().add_modifier(Modifier::REVERSED)
+                    } else {
+                        Style::default().add_modifier(Modifier::DIM)
+                    };
+                    title_spans.push(Span::styled(format!(" {} ", label), style));
+                    title_spans.push(Span::raw(" "));
+                }
+            }
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .title(Line::from(title_spans));
             let inner = layout.term_inner;
 
-            let display_lines = ui.pty.lines();
-            let paragraph = Paragraph::new(
-                display_lines
-                    .into_iter()
-                    .map(Line::from)
-                    .collect::<Vec<_>>(),
-            )
-            .wrap(Wrap { trim: false });
             f.render_widget(block, term_area);
-            f.render_widget(paragraph, inner);
-
-            if ui.focus == Focus::Terminal {
-                if let Some(cursor) = ui.pty.cursor_state() {
-                    if inner.width > 0 && inner.height > 0 && cursor.draw {
-                        let col = cursor.col;
-                        let row = cursor.row;
-                        let clamped_col = col.min(inner.width.saturating_sub(1));
-                        let clamped_row = row.min(inner.height.saturating_sub(1));
-                        let abs_x = inner.x + clamped_col;
-                        let abs_y = inner.y + clamped_row;
-                        final_cursor_abs = Some((abs_x, abs_y));
-                        f.set_cursor(abs_x, abs_y);
-                    }
+            f.render_widget(TerminalView::new(ui.terms.active().screen()), inner);
+            for &(row, from, to, current) in &search_cells {
+                let area = Rect {
+                    x: inner.x + from,
+                    y: inner.y + row,
+                    width: to.saturating_sub(from),
+                    height: 1,
+                }
+                .intersection(inner);
+                let bg = if current {
+                    Color::LightRed
+                } else {
+                    Color::Yellow
+                };
+                f.buffer_mut()
+                    .set_style(area, Style::default().fg(Color::Black).bg(bg));
+            }
+            for &(row, from, to) in &selection_cells {
+                let area = Rect {
+                    x: inner.x + from,
+                    y: inner.y + row,
+                    width: to - from,
+                    height: 1,
                 }
+                .intersection(inner);
+                f.buffer_mut()
+                    .set_style(area, Style::default().add_modifier(Modifier::REVERSED));
+            }
+
+            if let Some((row, col)) = selection_cursor
+                && row < inner.height
+                && col < inner.width
+            {
+                final_cursor_abs = Some((inner.x + col, inner.y + row));
+                f.set_cursor(inner.x + col, inner.y + row);
+            } else if ui.selection.is_none()
+                && ui.term_search.is_none()
+                && ui.focus == Focus::Terminal
+                && let Some(cursor) = ui.terms.active().cursor_state()
+                && inner.width > 0
+                && inner.height > 0
+                && cursor.draw
+            {
+                let col = cursor.col;
+                let row = cursor.row;
+                let clamped_col = col.min(inner.width.saturating_sub(1));
+                let clamped_row = row.min(inner.height.saturating_sub(1));
+                let abs_x = inner.x + clamped_col;
+                let abs_y = inner.y + clamped_row;
+                final_cursor_abs = Some((abs_x, abs_y));
+                f.set_cursor(abs_x, abs_y);
             }
 
             if ui.debug_overlay {
-                let debug = Paragraph::new(debug_lines(&ui, &layout, inner, final_cursor_abs))
+                let debug = Paragraph::new(debug_lines(ui, &layout, inner, final_cursor_abs))
                     .block(Block::default().borders(Borders::ALL).title(" debug "));
                 let overlay_area = Rect {
                     x: inner.x.saturating_add(1),
@@ -330,37 +746,59 @@ fn run_loop(
             let items = log_items[start..end]
                 .iter()
                 .enumerate()
-                .map(|(idx, line)| {
-                    let sliced = slice_from_col(line, ui.log_scroll_x, log_inner_width);
-                    let mut item = ListItem::new(Line::from(Span::raw(sliced)));
+                .map(|(idx, (line, ranges))| {
+                    let spans = highlighted_spans(line, ranges, ui.log_scroll_x, log_inner_width);
+                    let mut item = ListItem::new(Line::from(spans));
                     if start + idx == ui.selected_log_index {
                         item = item.style(Style::default().add_modifier(Modifier::REVERSED));
                     }
                     item
                 })
                 .collect::<Vec<_>>();
-            let log_block =
-                List::new(items).block(Block::default().borders(Borders::ALL).title(" Logs "));
+            let log_block = List::new(items).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(tr!("tui.title.logs")),
+            );
             f.render_widget(log_block, layout.logs);
 
             // Input bar
+            let keys = &ui.keys;
             let input_block =
                 Block::default()
                     .borders(Borders::ALL)
                     .title(match (ui.focus, ui.mode) {
-                        (Focus::LogInput, InputMode::EditingLog) => {
-                            " Enter log (Enter=save, Esc=cancel) "
-                        }
-                        (Focus::LogInput, InputMode::Normal) => {
-                            " Log input (i=add, e=edit, d=del, /=search, v=voice, Esc=switch, q=quit) "
+                        (Focus::LogInput, InputMode::EditingLog)
+                            if ui.pending_snippet.is_some() =>
+                        {
+                            tr!(
+                                "tui.title.snippet",
+                                lines = ui
+                                    .pending_snippet
+                                    .as_deref()
+                                    .map_or(0, |s| s.lines().count())
+                            )
                         }
-                        (Focus::LogInput, InputMode::ConfirmDelete) => {
-                            " Confirm delete (y/n) "
+                        (Focus::LogInput, InputMode::EditingLog) if ui.pending_cast.is_some() => {
+                            tr!("tui.title.cast")
                         }
-                        (Focus::LogInput, InputMode::Searching) => {
-                            " Search (Enter=apply, Esc=clear) "
-                        }
-                        _ => " Log input (Esc to focus) ",
+                        (Focus::LogInput, InputMode::EditingLog) => tr!("tui.title.editing"),
+                        (Focus::LogInput, InputMode::Normal) => tr!(
+                            "tui.title.normal",
+                            add = keys.add,
+                            edit = keys.edit,
+                            delete = keys.delete,
+                            search = keys.search,
+                            voice = keys.voice,
+                            toggle = keys.toggle_focus,
+                            quit = keys.quit
+                        ),
+                        (Focus::LogInput, InputMode::ConfirmDelete) => tr!("tui.title.confirm"),
+                        (Focus::LogInput, InputMode::Searching) => tr!("tui.title.search"),
+                        _ => tr!(
+                            "tui.title.unfocused",
+                            toggle = keys.in_terminal(keys.toggle_focus)
+                        ),
                     });
 
             let (input_text, cursor_col) = match ui.mode {
@@ -377,7 +815,9 @@ fn run_loop(
                         }
                         let sliced =
                             slice_from_col(&ui.log_input, ui.input_scroll_x, input_inner_width);
-                        let cursor = cursor_width.saturating_sub(ui.input_scroll_x).min(max_visible);
+                        let cursor = cursor_width
+                            .saturating_sub(ui.input_scroll_x)
+                            .min(max_visible);
                         (sliced, Some(cursor as u16))
                     }
                 }
@@ -394,19 +834,18 @@ fn run_loop(
                         }
                         let sliced =
                             slice_from_col(&ui.search_query, ui.search_scroll_x, input_inner_width);
-                        let cursor = cursor_width.saturating_sub(ui.search_scroll_x).min(max_visible);
+                        let cursor = cursor_width
+                            .saturating_sub(ui.search_scroll_x)
+                            .min(max_visible);
                         (sliced, Some(cursor as u16))
                     }
                 }
-                InputMode::ConfirmDelete => (
-                    "정말 이 로그를 삭제할까요? [y] 삭제 / [n] 취소".to_string(),
-                    None,
-                ),
+                InputMode::ConfirmDelete => (tr!("tui.confirm_delete"), None),
                 _ => {
                     if let Some((ref msg, _, _)) = ui.status_message {
                         (msg.clone(), None)
                     } else if ui.voice_task.is_some() && !ui.voice_stopping {
-                        ("녹음중... v 누르면 종료".to_string(), None)
+                        (tr!("tui.recording", key = keys.voice), None)
                     } else {
                         (String::new(), None)
                     }
@@ -417,51 +856,111 @@ fn run_loop(
 
             if matches!(ui.mode, InputMode::EditingLog | InputMode::Searching)
                 && ui.focus == Focus::LogInput
+                && let Some(col) = cursor_col
             {
-                if let Some(col) = cursor_col {
-                    f.set_cursor(layout.input.x + col + 1, layout.input.y + 1);
-                }
+                f.set_cursor(layout.input.x + col + 1, layout.input.y + 1);
+            }
+
+            if let Some(status) = ui.terms.active().exit_status()
+                && !inner.is_empty()
+            {
+                render_shell_exited(
+                    f,
+                    inner,
+                    status,
+                    ui.terms.active().last_cwd(),
+                    &ui.keys,
+                    ui.terms.len() > 1,
+                );
+            }
+
+            if let Some(recap) = &ui.recap {
+                render_recap(f, recap);
             }
         })?;
 
         if event::poll(Duration::from_millis(50))? {
             match event::read()? {
                 Event::Key(key) => {
+                    let keys = ui.keys.clone();
                     if ui.voice_task.is_some() {
                         if let Some(signal) = ui.voice_signal.as_ref() {
-                            let value = if key.code == KeyCode::Char('v') {
+                            let value = if keys.voice.matches(&key) {
                                 voice::RECORD_SIGNAL_STOP
                             } else {
                                 voice::RECORD_SIGNAL_CANCEL
                             };
                             let was_set = signal.swap(value, Ordering::Relaxed);
                             if was_set == 0 && value == voice::RECORD_SIGNAL_CANCEL {
-                                ui.set_status("녹음 취소됨");
+                                ui.set_status(tr!("tui.recording_cancelled"));
                             }
                             if was_set == 0 && value == voice::RECORD_SIGNAL_STOP {
                                 ui.voice_stopping = true;
-                                ui.set_status_for("로그 저장중입니다", Duration::from_secs(300));
+                                ui.set_status_for(tr!("tui.saving"), Duration::from_secs(300));
                             }
                         }
-                        if key.code == KeyCode::Char('v') {
+                        if keys.voice.matches(&key) {
                             continue;
                         }
                     }
+                    if ui.recap.take().is_some() {
+                        continue;
+                    }
+                    if ui.selection.is_some() {
+                        handle_selection_key(ui, key);
+                        continue;
+                    }
+                    if ui.term_search.is_some() {
+                        handle_term_search_key(ui, key);
+                        continue;
+                    }
+                    // 리더 키를 정했으면 터미널에서는 리더 다음 키만 단축키로 보고 나머지는 쉘로 보낸다
+                    if ui.focus == Focus::Terminal
+                        && ui.mode == InputMode::Normal
+                        && ui.terms.active().exit_status().is_none()
+                    {
+                        match keys.route(&key, &mut ui.leader_pending) {
+                            Route::Tui => {}
+                            Route::Leader => continue,
+                            Route::Shell => {
+                                send_terminal_key(ui, key);
+                                continue;
+                            }
+                        }
+                    } else {
+                        ui.leader_pending = false;
+                    }
+                    if keys.term_search.matches(&key) && ui.mode == InputMode::Normal {
+                        ui.term_search = Some(TermSearch::default());
+                        ui.focus = Focus::Terminal;
+                        continue;
+                    }
+                    if keys.select.matches(&key) && ui.mode == InputMode::Normal {
+                        let pty = ui.terms.active_mut();
+                        let (rows, _) = pty.size();
+                        let (row, col) = pty
+                            .cursor_state()
+                            .map_or((rows.saturating_sub(1), 0), |cursor| {
+                                (cursor.row, cursor.col)
+                            });
+                        ui.selection = Some(Selection::at(pty.view_to_history(row, col)));
+                        ui.focus = Focus::Terminal;
+                        continue;
+                    }
                     if ui.mode == InputMode::ConfirmDelete {
                         match key.code {
                             KeyCode::Char('y') => {
-                                if let Some(item) = log_items_filtered.get(ui.selected_log_index) {
-                                    if let Ok(true) = app.log_store.delete_by_id(
-                                        &app.current_branch,
-                                        &item.id,
-                                    ) {
-                                        ui.set_status("log deleted");
-                                        let next_len = log_items_filtered.len().saturating_sub(1);
-                                        if next_len == 0 {
-                                            ui.selected_log_index = 0;
-                                        } else if ui.selected_log_index >= next_len {
-                                            ui.selected_log_index = next_len - 1;
-                                        }
+                                if let Some(item) = log_items_filtered.get(ui.selected_log_index)
+                                    && let Ok(true) =
+                                        app.log_store.delete_by_id(&app.current_branch, &item.id)
+                                {
+                                    ui.logs_dirty = true;
+                                    ui.set_status(tr!("tui.log_deleted"));
+                                    let next_len = log_items_filtered.len().saturating_sub(1);
+                                    if next_len == 0 {
+                                        ui.selected_log_index = 0;
+                                    } else if ui.selected_log_index >= next_len {
+                                        ui.selected_log_index = next_len - 1;
                                     }
                                 }
                                 ui.mode = InputMode::Normal;
@@ -473,7 +972,7 @@ fn run_loop(
                         }
                         continue;
                     }
-                    if key.code == KeyCode::Esc
+                    if keys.toggle_focus.matches(&key)
                         && !(ui.focus == Focus::LogInput
                             && matches!(
                                 ui.mode,
@@ -489,51 +988,154 @@ fn run_loop(
                         continue;
                     }
 
-                    match key.code {
-                        KeyCode::Char('q')
-                            if ui.focus == Focus::LogInput && ui.mode == InputMode::Normal =>
-                        {
-                            break
+                    if keys.quit.matches(&key)
+                        && ui.focus == Focus::LogInput
+                        && ui.mode == InputMode::Normal
+                    {
+                        break;
+                    }
+                    if keys.debug.matches(&key) {
+                        ui.debug_overlay = !ui.debug_overlay;
+                    }
+                    if keys.log_command.matches(&key) {
+                        let Some(tracker) = ui.terms.active().command_tracker() else {
+                            ui.set_status_for(
+                                tr!("tui.shell_integration_off"),
+                                Duration::from_secs(4),
+                            );
+                            continue;
+                        };
+                        let Some(note) = tracker.last().map(|record| record.to_note()) else {
+                            ui.set_status(tr!("tui.no_command"));
+                            continue;
+                        };
+                        match app.log_store.append_text(&app.current_branch, &note) {
+                            Ok(_) => {
+                                ui.logs_dirty = true;
+                                ui.set_status(tr!("tui.command_saved"));
+                            }
+                            Err(e) => ui.set_status(tr!("tui.command_log_failed", error = e)),
                         }
-                        KeyCode::F(2) => {
-                            ui.debug_overlay = !ui.debug_overlay;
+                        continue;
+                    }
+                    if ui.mode == InputMode::Normal {
+                        if keys.new_tab.matches(&key) {
+                            match ui.terms.open() {
+                                Ok(()) => ui.focus = Focus::Terminal,
+                                Err(e) => ui.set_status(tr!("tui.tab_open_failed", error = e)),
+                            }
+                            continue;
+                        }
+                        if keys.close_tab.matches(&key) {
+                            // 닫히는 탭의 녹화는 끝내고 설명을 받는다
+                            let recording = match ui.terms.len() {
+                                1 => None,
+                                _ => ui.terms.active_mut().stop_recording(),
+                            };
+                            if !ui.terms.close_active() {
+                                ui.set_status(tr!("tui.tab_last"));
+                            }
+                            if let Some(result) = recording {
+                                finish_recording(ui, result);
+                            }
+                            continue;
+                        }
+                        // 배치 키 기본값(Alt+l 등)은 쉘의 줄 편집 키라서 리더가 없으면 쉘에 양보한다
+                        let layout_keys = ui.focus != Focus::Terminal
+                            || ui.terms.active().exit_status().is_some()
+                            || keys.layout_keys_in_terminal();
+                        if layout_keys && keys.zoom.matches(&key) {
+                            ui.layout.zoom = !ui.layout.zoom;
+                            continue;
+                        }
+                        if layout_keys && keys.layout.matches(&key) {
+                            ui.layout.preset = ui.layout.preset.next();
+                            ui.layout.zoom = false;
+                            if !ui.layout.shows_terminal(Panel::Terminal) {
+                                ui.focus = Focus::LogInput;
+                            }
+                            let name = ui.layout.preset.label();
+                            ui.set_status(tr!("tui.layout_changed", layout = name));
+                            continue;
+                        }
+                        if layout_keys && (keys.grow.matches(&key) || keys.shrink.matches(&key)) {
+                            if keys.grow.matches(&key) {
+                                ui.layout.grow();
+                            } else {
+                                ui.layout.shrink();
+                            }
+                            ui.set_status(tr!("tui.layout_split", percent = ui.layout.split));
+                            continue;
+                        }
+                        if keys.record.matches(&key) {
+                            if let Some(result) = ui.terms.active_mut().stop_recording() {
+                                finish_recording(ui, result);
+                                continue;
+                            }
+                            let started = app
+                                .log_store
+                                .new_cast_path()
+                                .and_then(|path| ui.terms.active_mut().start_recording(&path));
+                            match started {
+                                Ok(()) => ui.set_status_for(
+                                    tr!("tui.cast_started", key = keys.in_terminal(keys.record)),
+                                    Duration::from_secs(4),
+                                ),
+                                Err(e) => ui.set_status(tr!("tui.cast_failed", error = e)),
+                            }
+                            continue;
+                        }
+                        if keys.next_tab.matches(&key) {
+                            ui.terms.next();
+                            continue;
+                        }
+                        if keys.prev_tab.matches(&key) {
+                            ui.terms.prev();
+                            continue;
                         }
-                        _ => {}
                     }
 
                     match ui.focus {
-                        Focus::Terminal => {
-                            match key.code {
-                                KeyCode::Up if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                    ui.pty.scroll_up(1);
+                        // 쉘이 끝난 탭: 저장소 루트나 마지막 디렉터리에서 다시 시작하거나,
+                        // 다른 탭이 있으면 이 탭만 닫고 마지막 탭이면 앱을 끝낸다
+                        Focus::Terminal if ui.terms.active().exit_status().is_some() => {
+                            let cwd = if keys.restart_shell.matches(&key) {
+                                None
+                            } else if keys.restart_shell_cwd.matches(&key) {
+                                ui.terms.active().last_cwd().cloned()
+                            } else if keys.quit.matches(&key) {
+                                if ui.terms.len() == 1 {
+                                    break;
                                 }
-                                KeyCode::Down if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                    ui.pty.scroll_down(1);
-                                }
-                                KeyCode::PageUp => ui.pty.scroll_up(5),
-                                KeyCode::PageDown => ui.pty.scroll_down(5),
-                                _ => {
-                                    if let Some(bytes) = encode_key_event(key) {
-                                        ui.pty.send_bytes(&bytes);
-                                    }
+                                let recording = ui.terms.active_mut().stop_recording();
+                                ui.terms.close_active();
+                                if let Some(result) = recording {
+                                    finish_recording(ui, result);
                                 }
+                                continue;
+                            } else {
+                                continue;
+                            };
+                            if let Err(e) = ui.terms.respawn_active(cwd) {
+                                ui.set_status(tr!("tui.shell_restart_failed", error = e));
                             }
                         }
+                        Focus::Terminal => send_terminal_key(ui, key),
                         Focus::LogInput => match ui.mode {
                             InputMode::Normal => match key.code {
-                                KeyCode::Char('i') => {
+                                _ if keys.add.matches(&key) => {
                                     ui.mode = InputMode::EditingLog;
                                     ui.log_input.clear();
                                     ui.input_cursor = 0;
                                     ui.input_scroll_x = 0;
                                     ui.editing_log_id = None;
                                 }
-                                KeyCode::Char('d') => {
-                                    if !log_items_filtered.is_empty() {
-                                        ui.mode = InputMode::ConfirmDelete;
-                                    }
+                                _ if keys.delete.matches(&key)
+                                    && !log_items_filtered.is_empty() =>
+                                {
+                                    ui.mode = InputMode::ConfirmDelete;
                                 }
-                                KeyCode::Char('e') => {
+                                _ if keys.edit.matches(&key) => {
                                     if let Some(item) =
                                         log_items_filtered.get(ui.selected_log_index)
                                     {
@@ -549,7 +1151,7 @@ fn run_loop(
                                         ui.editing_log_id = Some(item.id.clone());
                                     }
                                 }
-                                KeyCode::Char('/') => {
+                                _ if keys.search.matches(&key) => {
                                     ui.mode = InputMode::Searching;
                                     ui.search_cursor = ui.search_query.chars().count();
                                     ui.search_scroll_x = adjust_input_scroll(
@@ -559,26 +1161,28 @@ fn run_loop(
                                         ui.search_scroll_x,
                                     );
                                 }
-                                KeyCode::Char('v') => {
-                                    if ui.voice_task.is_none() {
-                                        let (tx, rx) = mpsc::channel::<VoiceEvent>();
-                                        let signal = Arc::new(AtomicU8::new(0));
-                                        ui.voice_task = Some(rx);
-                                        ui.voice_signal = Some(signal.clone());
-                                        std::thread::spawn(move || {
-                                            let status_tx = tx.clone();
-                                            let result = match voice::model::prepare_model_path_with_status(
+                                _ if keys.voice.matches(&key) && ui.voice_task.is_none() => {
+                                    let (tx, rx) = mpsc::channel::<VoiceEvent>();
+                                    let signal = Arc::new(AtomicU8::new(0));
+                                    let voice_config = app.config.voice.clone();
+                                    let recording_msg = tr!("tui.recording", key = keys.voice);
+                                    ui.voice_task = Some(rx);
+                                    ui.voice_signal = Some(signal.clone());
+                                    std::thread::spawn(move || {
+                                        let status_tx = tx.clone();
+                                        let result =
+                                            match voice::model::prepare_model_path_with_status(
+                                                &voice_config.model_url,
                                                 |msg| {
-                                                    let _ = status_tx.send(VoiceEvent::Status(
-                                                        msg.to_string(),
-                                                    ));
+                                                    let _ = status_tx
+                                                        .send(VoiceEvent::Status(msg.to_string()));
                                                 },
                                             ) {
                                                 Ok(model) => {
                                                     if signal.load(Ordering::Relaxed)
                                                         == voice::RECORD_SIGNAL_CANCEL
                                                     {
-                                                        Err("녹음이 취소되었습니다".to_string())
+                                                        Err(tr!("voice.cancelled"))
                                                     } else {
                                                         if model.downloaded {
                                                             std::thread::sleep(
@@ -588,38 +1192,33 @@ fn run_loop(
                                                         if signal.load(Ordering::Relaxed)
                                                             == voice::RECORD_SIGNAL_CANCEL
                                                         {
-                                                            Err(
-                                                                "녹음이 취소되었습니다".to_string()
-                                                            )
+                                                            Err(tr!("voice.cancelled"))
                                                         } else {
                                                             let _ = tx.send(VoiceEvent::Status(
-                                                                "녹음중... v 누르면 종료"
-                                                                    .to_string(),
+                                                                recording_msg,
                                                             ));
                                                             voice::transcribe_from_mic_until_signal(
                                                                 &model.path,
+                                                                &voice_config.language,
                                                                 signal,
                                                             )
                                                         }
                                                     }
                                                 }
-                                                Err(err) => Err(format!(
-                                                    "모델 준비 실패: {}",
-                                                    err
-                                                )),
+                                                Err(err) => {
+                                                    Err(tr!("voice.model_failed", error = err))
+                                                }
                                             };
-                                            let _ = tx.send(VoiceEvent::Result(result));
-                                        });
-                                    }
+                                        let _ = tx.send(VoiceEvent::Result(result));
+                                    });
                                 }
                                 KeyCode::Up => {
-                                    ui.selected_log_index =
-                                        ui.selected_log_index.saturating_sub(1);
+                                    ui.selected_log_index = ui.selected_log_index.saturating_sub(1);
                                 }
-                                KeyCode::Down => {
-                                    if ui.selected_log_index + 1 < log_items_filtered.len() {
-                                        ui.selected_log_index += 1;
-                                    }
+                                KeyCode::Down
+                                    if ui.selected_log_index + 1 < log_items_filtered.len() =>
+                                {
+                                    ui.selected_log_index += 1;
                                 }
                                 KeyCode::PageUp => {
                                     let step = log_inner_height.max(1);
@@ -658,25 +1257,21 @@ fn run_loop(
                                 KeyCode::Enter => {
                                     ui.mode = InputMode::Normal;
                                 }
-                                KeyCode::Backspace => {
-                                    if ui.search_cursor > 0 {
-                                        let idx = byte_index_from_char(
-                                            &ui.search_query,
-                                            ui.search_cursor - 1,
-                                        );
-                                        let next_idx = byte_index_from_char(
-                                            &ui.search_query,
-                                            ui.search_cursor,
-                                        );
-                                        ui.search_query.replace_range(idx..next_idx, "");
-                                        ui.search_cursor -= 1;
-                                        ui.search_scroll_x = adjust_input_scroll(
-                                            &ui.search_query,
-                                            ui.search_cursor,
-                                            input_inner_width,
-                                            ui.search_scroll_x,
-                                        );
-                                    }
+                                KeyCode::Backspace if ui.search_cursor > 0 => {
+                                    let idx = byte_index_from_char(
+                                        &ui.search_query,
+                                        ui.search_cursor - 1,
+                                    );
+                                    let next_idx =
+                                        byte_index_from_char(&ui.search_query, ui.search_cursor);
+                                    ui.search_query.replace_range(idx..next_idx, "");
+                                    ui.search_cursor -= 1;
+                                    ui.search_scroll_x = adjust_input_scroll(
+                                        &ui.search_query,
+                                        ui.search_cursor,
+                                        input_inner_width,
+                                        ui.search_scroll_x,
+                                    );
                                 }
                                 KeyCode::Delete => {
                                     let len = ui.search_query.chars().count();
@@ -735,10 +1330,8 @@ fn run_loop(
                                     );
                                 }
                                 KeyCode::Char(c) => {
-                                    let idx = byte_index_from_char(
-                                        &ui.search_query,
-                                        ui.search_cursor,
-                                    );
+                                    let idx =
+                                        byte_index_from_char(&ui.search_query, ui.search_cursor);
                                     ui.search_query.insert(idx, c);
                                     ui.search_cursor += 1;
                                     ui.search_scroll_x = adjust_input_scroll(
@@ -752,6 +1345,11 @@ fn run_loop(
                             },
                             InputMode::EditingLog => match key.code {
                                 KeyCode::Esc => {
+                                    if let Some(cast) = ui.pending_cast.take() {
+                                        let _ = std::fs::remove_file(cast);
+                                        ui.set_status(tr!("tui.cast_discarded"));
+                                    }
+                                    ui.pending_snippet = None;
                                     ui.mode = InputMode::Normal;
                                     ui.log_input.clear();
                                     ui.input_cursor = 0;
@@ -759,20 +1357,53 @@ fn run_loop(
                                     ui.editing_log_id = None;
                                 }
                                 KeyCode::Enter => {
-                                    if !ui.log_input.trim().is_empty() {
+                                    if let Some(cast) = ui.pending_cast.take() {
+                                        let caption = match ui.log_input.trim() {
+                                            "" => tr!("tui.cast_caption"),
+                                            caption => caption.to_string(),
+                                        };
+                                        match app.log_store.append_cast(
+                                            &app.current_branch,
+                                            &caption,
+                                            &cast,
+                                        ) {
+                                            Ok(_) => ui.set_status(tr!("tui.saved")),
+                                            Err(e) => {
+                                                ui.set_status(tr!("tui.cast_failed", error = e))
+                                            }
+                                        }
+                                        ui.logs_dirty = true;
+                                    } else if let Some(snippet) = ui.pending_snippet.take() {
+                                        let caption = match ui.log_input.trim() {
+                                            "" => tr!("tui.snippet_caption"),
+                                            caption => caption.to_string(),
+                                        };
+                                        match app.log_store.append_snippet(
+                                            &app.current_branch,
+                                            &caption,
+                                            &snippet,
+                                        ) {
+                                            Ok(_) => ui.set_status(tr!("tui.saved")),
+                                            Err(e) => {
+                                                ui.set_status(tr!("tui.snippet_failed", error = e))
+                                            }
+                                        }
+                                        ui.logs_dirty = true;
+                                    } else if !ui.log_input.trim().is_empty() {
                                         if let Some(id) = ui.editing_log_id.take() {
                                             if let Ok(true) = app.log_store.update_text_by_id(
                                                 &app.current_branch,
                                                 &id,
                                                 &ui.log_input,
                                             ) {
-                                                ui.set_status("log updated");
+                                                ui.set_status(tr!("tui.log_updated"));
                                             }
                                         } else {
                                             let _ = app
                                                 .log_store
                                                 .append_text(&app.current_branch, &ui.log_input);
                                         }
+                                        ui.logs_dirty = true;
                                     } else {
                                         ui.editing_log_id = None;
                                     }
@@ -781,23 +1412,19 @@ fn run_loop(
                                     ui.input_cursor = 0;
                                     ui.input_scroll_x = 0;
                                 }
-                                KeyCode::Backspace => {
-                                    if ui.input_cursor > 0 {
-                                        let idx = byte_index_from_char(
-                                            &ui.log_input,
-                                            ui.input_cursor - 1,
-                                        );
-                                        let next_idx =
-                                            byte_index_from_char(&ui.log_input, ui.input_cursor);
-                                        ui.log_input.replace_range(idx..next_idx, "");
-                                        ui.input_cursor -= 1;
-                                        ui.input_scroll_x = adjust_input_scroll(
-                                            &ui.log_input,
-                                            ui.input_cursor,
-                                            input_inner_width,
-                                            ui.input_scroll_x,
-                                        );
-                                    }
+                                KeyCode::Backspace if ui.input_cursor > 0 => {
+                                    let idx =
+                                        byte_index_from_char(&ui.log_input, ui.input_cursor - 1);
+                                    let next_idx =
+                                        byte_index_from_char(&ui.log_input, ui.input_cursor);
+                                    ui.log_input.replace_range(idx..next_idx, "");
+                                    ui.input_cursor -= 1;
+                                    ui.input_scroll_x = adjust_input_scroll(
+                                        &ui.log_input,
+                                        ui.input_cursor,
+                                        input_inner_width,
+                                        ui.input_scroll_x,
+                                    );
                                 }
                                 KeyCode::Delete => {
                                     let len = ui.log_input.chars().count();
@@ -854,8 +1481,7 @@ fn run_loop(
                                     );
                                 }
                                 KeyCode::Char(c) => {
-                                    let idx =
-                                        byte_index_from_char(&ui.log_input, ui.input_cursor);
+                                    let idx = byte_index_from_char(&ui.log_input, ui.input_cursor);
                                     ui.log_input.insert(idx, c);
                                     ui.input_cursor += 1;
                                     ui.input_scroll_x = adjust_input_scroll(
@@ -870,11 +1496,40 @@ fn run_loop(
                         },
                     }
                 }
-                Event::Mouse(mouse) => match mouse.kind {
-                    MouseEventKind::ScrollUp => ui.pty.scroll_up(3),
-                    MouseEventKind::ScrollDown => ui.pty.scroll_down(3),
-                    _ => {}
-                },
+                Event::Mouse(mouse) => handle_mouse(ui, &layout, mouse),
+                Event::Paste(text) if ui.recap.is_none() && ui.voice_task.is_none() => {
+                    if let Some(search) = ui.term_search.as_mut() {
+                        search
+                            .query
+                            .push_str(text.lines().next().unwrap_or_default());
+                        update_term_search(search, ui.terms.active_mut());
+                        continue;
+                    }
+                    match (ui.focus, ui.mode) {
+                        (Focus::Terminal, InputMode::Normal) if ui.selection.is_none() => {
+                            ui.terms.active_mut().paste(&text)
+                        }
+                        (Focus::LogInput, InputMode::EditingLog) => {
+                            insert_pasted(&mut ui.log_input, &mut ui.input_cursor, &text);
+                            ui.input_scroll_x = adjust_input_scroll(
+                                &ui.log_input,
+                                ui.input_cursor,
+                                input_inner_width,
+                                ui.input_scroll_x,
+                            );
+                        }
+                        (Focus::LogInput, InputMode::Searching) => {
+                            insert_pasted(&mut ui.search_query, &mut ui.search_cursor, &text);
+                            ui.search_scroll_x = adjust_input_scroll(
+                                &ui.search_query,
+                                ui.search_cursor,
+                                input_inner_width,
+                                ui.search_scroll_x,
+                            );
+                        }
+                        _ => {}
+                    }
+                }
                 Event::Resize(_, _) => {}
                 _ => {}
             }
@@ -883,43 +1538,412 @@ fn run_loop(
     Ok(())
 }
 
-struct LayoutInfo {
-    header: Rect,
-    terminal: Rect,
-    logs: Rect,
-    input: Rect,
-    term_inner: Rect,
+/// 클릭한 패널로 포커스를 옮기고, 자식 프로그램이 마우스 보고를 켰으면 터미널 이벤트를
+/// 그대로 넘긴다. 보고가 꺼져 있으면 휠은 스크롤백, 드래그는 선택 (Shift를 누르면 항상 선택).
+fn handle_mouse(ui: &mut UiState, layout: &LayoutInfo, mouse: MouseEvent) {
+    let inner = layout.term_inner;
+    let at = (mouse.column, mouse.row).into();
+    let in_terminal = inner.contains(at);
+    let idle = ui.mode == InputMode::Normal && ui.recap.is_none();
+
+    if let MouseEventKind::Down(_) = mouse.kind
+        && idle
+    {
+        if layout.terminal.contains(at) {
+            ui.focus = Focus::Terminal;
+        } else if layout.logs.contains(at) || layout.input.contains(at) {
+            ui.focus = Focus::LogInput;
+        }
+    }
+
+    if idle
+        && ui.selection.is_none()
+        && ui.terms.active().mouse_reporting()
+        && !mouse.modifiers.contains(KeyModifiers::SHIFT)
+        && inner.width > 0
+        && inner.height > 0
+    {
+        // 누른 채 패널 밖으로 나가도 놓는 위치는 가장자리로 전달
+        let follows_press = matches!(mouse.kind, MouseEventKind::Up(_) | MouseEventKind::Drag(_));
+        if in_terminal || follows_press {
+            let row = mouse.row.clamp(inner.y, inner.bottom() - 1) - inner.y;
+            let col = mouse.column.clamp(inner.x, inner.right() - 1) - inner.x;
+            ui.terms
+                .active_mut()
+                .send_mouse(mouse.kind, mouse.modifiers, col, row);
+            return;
+        }
+    }
+
+    match mouse.kind {
+        MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+            let up = mouse.kind == MouseEventKind::ScrollUp;
+            let pty = ui.terms.active_mut();
+            if pty.alternate_screen() {
+                // 전체 화면 프로그램(less 등)은 스크롤백이 없으니 방향키로 넘긴다
+                let code = if up { KeyCode::Up } else { KeyCode::Down };
+                for _ in 0..3 {
+                    pty.send_key(KeyEvent::from(code));
+                }
+            } else if up {
+                pty.scroll_up(3);
+            } else {
+                pty.scroll_down(3);
+            }
+        }
+        // 터미널 패널에서 드래그하면 선택, 그냥 클릭이면 선택 해제
+        MouseEventKind::Down(MouseButton::Left) if idle && in_terminal => {
+            let pos = ui
+                .terms
+                .active_mut()
+                .view_to_history(mouse.row - inner.y, mouse.column - inner.x);
+            ui.selection = Some(Selection {
+                anchor: Some(pos),
+                cursor: pos,
+            });
+        }
+        MouseEventKind::Drag(MouseButton::Left) => {
+            if let Some(selection) = ui.selection.as_mut()
+                && inner.width > 0
+                && inner.height > 0
+            {
+                // 패널 밖으로 끌면 그 방향으로 스크롤
+                if mouse.row < inner.y {
+                    ui.terms.active_mut().scroll_up(1);
+                } else if mouse.row >= inner.bottom() {
+                    ui.terms.active_mut().scroll_down(1);
+                }
+                let row = mouse.row.clamp(inner.y, inner.bottom() - 1) - inner.y;
+                let col = mouse.column.clamp(inner.x, inner.right() - 1) - inner.x;
+                selection.cursor = ui.terms.active_mut().view_to_history(row, col);
+            }
+        }
+        MouseEventKind::Up(MouseButton::Left) => {
+            if let Some(selection) = ui.selection
+                && selection.anchor == Some(selection.cursor)
+            {
+                ui.selection = None;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 터미널 포커스에서 단축키가 아닌 키는 쉘로 보낸다 (스크롤 키는 스크롤백을 움직임)
+fn send_terminal_key(ui: &mut UiState, key: KeyEvent) {
+    let pty = ui.terms.active_mut();
+    // 전체 화면 프로그램에는 스크롤백이 없으니 스크롤 키도 그대로 넘긴다
+    let scrollback = !pty.alternate_screen();
+    let ctrl = key.modifiers == KeyModifiers::CONTROL;
+    match key.code {
+        KeyCode::Up if scrollback && ctrl => pty.scroll_up(1),
+        KeyCode::Down if scrollback && ctrl => pty.scroll_down(1),
+        KeyCode::PageUp if scrollback && key.modifiers.is_empty() => pty.scroll_up(5),
+        KeyCode::PageDown if scrollback && key.modifiers.is_empty() => pty.scroll_down(5),
+        _ => pty.send_key(key),
+    }
+}
+
+/// 선택 모드 키: 방향키/PageUp/PageDown/Home/End로 이동, Space(v)로 시작점 지정,
+/// Enter(y)로 선택을 마치고 설명 입력, Esc(q)로 취소
+fn handle_selection_key(ui: &mut UiState, key: KeyEvent) {
+    let Some(mut selection) = ui.selection else {
+        return;
+    };
+    let (rows, cols) = ui.terms.active().size();
+    let last_row = ui.terms.active_mut().history_rows().saturating_sub(1);
+    let page = usize::from(rows.max(1));
+    let cursor = selection.cursor;
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            ui.selection = None;
+            ui.terms.active_mut().scroll_down(usize::MAX);
+            return;
+        }
+        KeyCode::Enter | KeyCode::Char('y') => {
+            finish_selection(ui, &selection);
+            return;
+        }
+        KeyCode::Char(' ') | KeyCode::Char('v') => {
+            selection.anchor = match selection.anchor {
+                Some(_) => None,
+                None => Some(cursor),
+            };
+        }
+        KeyCode::Up => selection.cursor.row = cursor.row.saturating_sub(1),
+        KeyCode::Down => selection.cursor.row = (cursor.row + 1).min(last_row),
+        KeyCode::PageUp => selection.cursor.row = cursor.row.saturating_sub(page),
+        KeyCode::PageDown => selection.cursor.row = (cursor.row + page).min(last_row),
+        KeyCode::Left => selection.cursor.col = cursor.col.saturating_sub(1),
+        KeyCode::Right => selection.cursor.col = (cursor.col + 1).min(cols.saturating_sub(1)),
+        KeyCode::Home => selection.cursor.col = 0,
+        KeyCode::End => selection.cursor.col = cols.saturating_sub(1),
+        _ => {}
+    }
+    ui.terms.active_mut().scroll_into_view(selection.cursor.row);
+    ui.selection = Some(selection);
+}
+
+/// 스크롤백 검색 키: 글자를 치는 대로 찾고, Up/Down(Ctrl+p/n)으로 이전/다음 결과,
+/// Enter는 보던 자리에서 검색을 마치고, Esc는 맨 아래로 돌아간다
+fn handle_term_search_key(ui: &mut UiState, key: KeyEvent) {
+    let Some(search) = ui.term_search.as_mut() else {
+        return;
+    };
+    let pty = ui.terms.active_mut();
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let hit = match key.code {
+        KeyCode::Esc => {
+            ui.term_search = None;
+            pty.scroll_down(usize::MAX);
+            return;
+        }
+        KeyCode::Enter => {
+            ui.term_search = None;
+            return;
+        }
+        KeyCode::Up => search.older(),
+        KeyCode::Down => search.newer(),
+        KeyCode::Char('p') if ctrl => search.older(),
+        KeyCode::Char('n') if ctrl => search.newer(),
+        KeyCode::Backspace => {
+            search.query.pop();
+            update_term_search(search, pty);
+            return;
+        }
+        KeyCode::Char(c) if !ctrl => {
+            search.query.push(c);
+            update_term_search(search, pty);
+            return;
+        }
+        _ => return,
+    };
+    if let Some(hit) = hit {
+        pty.scroll_into_view(hit.row);
+    }
+}
+
+/// 검색어가 바뀌면 다시 찾고, 지금 보이는 화면 아래쪽에서 가장 가까운 결과로 옮긴다
+fn update_term_search(search: &mut TermSearch, pty: &mut PtyTerminal) {
+    let (rows, _) = pty.size();
+    let bottom = pty.view_to_history(rows.saturating_sub(1), 0).row;
+    let matches = pty.search(&search.query);
+    search.set_matches(matches, bottom);
+    if let Some(hit) = search.current_match() {
+        pty.scroll_into_view(hit.row);
+    }
+}
+
+fn term_search_title(search: &TermSearch) -> String {
+    match search.current {
+        Some(index) => tr!(
+            "tui.title.terminal_search",
+            query = search.query,
+            index = index + 1,
+            count = search.matches.len()
+        ),
+        None if search.query.is_empty() => tr!("tui.title.terminal_search_empty"),
+        None => tr!("tui.title.terminal_search_none", query = search.query),
+    }
+}
+
+/// 선택한 출력을 코드 블록으로 잡아 두고 로그 입력창에서 설명을 받는다
+fn finish_selection(ui: &mut UiState, selection: &Selection) {
+    let text = ui.terms.active_mut().selected_text(selection);
+    ui.selection = None;
+    ui.terms.active_mut().scroll_down(usize::MAX);
+    if text.trim().is_empty() {
+        ui.set_status(tr!("tui.selection_empty"));
+        return;
+    }
+    ui.pending_snippet = Some(text);
+    ui.focus = Focus::LogInput;
+    ui.mode = InputMode::EditingLog;
+    ui.log_input.clear();
+    ui.input_cursor = 0;
+    ui.input_scroll_x = 0;
+    ui.editing_log_id = None;
+}
+
+/// 배치 중 설정과 달라진 값만 그 값을 가져온 설정 파일(없으면 사용자 설정)에 저장한다.
+/// 환경 변수로 정한 값은 파일에 써도 다음 실행에 가려지므로 건너뛴다.
+fn save_layout(app: &AppState, layout: &PanelLayout) -> Result<(), String> {
+    let ui = &app.config.ui;
+    let changed = [
+        (
+            "ui.layout",
+            layout.preset.as_setting().to_string(),
+            ui.layout.trim().to_string(),
+        ),
+        ("ui.split", layout.split.to_string(), ui.split.to_string()),
+    ]
+    .into_iter()
+    .filter(|(_, now, before)| now != before)
+    .collect::<Vec<_>>();
+    if changed.is_empty() {
+        return Ok(());
+    }
+    let mut layers = ConfigLayers::load(&app.repo_root)?;
+    for (key, now, _) in changed {
+        let scope = match layers.get(key)?.source {
+            ConfigSource::Env => continue,
+            ConfigSource::Repo => ConfigScope::Repo,
+            ConfigSource::Default | ConfigSource::User => ConfigScope::User,
+        };
+        layers.set(scope, key, &now)?;
+    }
+    Ok(())
+}
+
+/// 녹화를 끝낸 파일을 잡아 두고 로그 입력창에서 설명을 받는다
+fn finish_recording(ui: &mut UiState, result: Result<PathBuf, String>) {
+    let path = match result {
+        Ok(path) => path,
+        Err(e) => {
+            ui.set_status(tr!("tui.cast_failed", error = e));
+            return;
+        }
+    };
+    ui.pending_snippet = None;
+    ui.pending_cast = Some(path);
+    ui.focus = Focus::LogInput;
+    ui.mode = InputMode::EditingLog;
+    ui.log_input.clear();
+    ui.input_cursor = 0;
+    ui.input_scroll_x = 0;
+    ui.editing_log_id = None;
+}
+
+/// 종료할 때 끝나지 않은 녹화는 기본 설명으로 현재 브랜치에 저장한다. 실패한 것의 오류를 돌려준다.
+fn save_recordings(app: &AppState, ui: &mut UiState) -> Vec<String> {
+    let mut casts = ui.terms.stop_recordings();
+    casts.extend(ui.pending_cast.take().map(Ok));
+    casts
+        .into_iter()
+        .filter_map(|cast| {
+            cast.and_then(|path| {
+                app.log_store
+                    .append_cast(&app.current_branch, &tr!("tui.cast_caption"), &path)
+            })
+            .err()
+        })
+        .collect()
+}
+
+/// 쉘이 끝난 탭의 터미널 패널 아래쪽에 종료 코드와 다시 시작하는 키를 띄운다
+fn render_shell_exited(
+    f: &mut Frame,
+    inner: Rect,
+    status: &ExitStatus,
+    cwd: Option<&PathBuf>,
+    keys: &KeyMap,
+    other_tabs: bool,
+) {
+    let mut lines = vec![Line::from(Span::styled(
+        tr!("tui.shell_exited", code = status.exit_code()),
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+    lines.push(Line::from(tr!(
+        "tui.shell_restart_root",
+        key = keys.restart_shell
+    )));
+    if let Some(cwd) = cwd {
+        lines.push(Line::from(tr!(
+            "tui.shell_restart_cwd",
+            key = keys.restart_shell_cwd,
+            dir = cwd.display()
+        )));
+    }
+    lines.push(Line::from(if other_tabs {
+        tr!("tui.shell_close_tab", key = keys.quit)
+    } else {
+        tr!("tui.shell_quit", key = keys.quit)
+    }));
+
+    let width = inner.width.min(72);
+    let height = (lines.len() as u16 + 2).min(inner.height);
+    let area = Rect {
+        x: inner.x + (inner.width - width) / 2,
+        y: inner.bottom() - height,
+        width,
+        height,
+    };
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL)),
+        area,
+    );
 }
 
-fn compute_layout(area: Rect) -> LayoutInfo {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Min(0),
-            Constraint::Length(3),
-        ])
-        .split(area);
-
-    let body = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-        .split(chunks[1]);
-
-    let term_area = body[0];
-    let term_inner = Rect {
-        x: term_area.x.saturating_add(1),
-        y: term_area.y.saturating_add(1),
-        width: term_area.width.saturating_sub(2),
-        height: term_area.height.saturating_sub(2),
+/// 화면 가운데에 브랜치 요약 창을 그린다
+fn render_recap(f: &mut Frame, recap: &Recap) {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let note_line = |item: &LogItem| {
+        Line::from(format!(
+            "  [{}] {}",
+            item.created_at.format("%m-%d %H:%M"),
+            item.text
+        ))
     };
 
-    LayoutInfo {
-        header: chunks[0],
-        terminal: term_area,
-        logs: body[1],
-        input: chunks[2],
-        term_inner,
+    let mut lines = vec![Line::from(match recap.since_last {
+        Some(elapsed) => tr!("tui.recap.last_note", elapsed = format_elapsed(elapsed)),
+        None => tr!("tui.recap.no_notes"),
+    })];
+    if !recap.recent.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(tr!("tui.recap.recent"), bold)));
+        lines.extend(recap.recent.iter().map(note_line));
+    }
+    if !recap.todos.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            tr!("tui.recap.todos", count = recap.open_todos),
+            bold,
+        )));
+        let hidden = recap.open_todos - recap.todos.len();
+        if hidden > 0 {
+            lines.push(Line::from(Span::styled(
+                tr!("tui.recap.more_todos", count = hidden),
+                Style::default().add_modifier(Modifier::DIM),
+            )));
+        }
+        lines.extend(recap.todos.iter().map(note_line));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        tr!("tui.recap.dismiss"),
+        Style::default().add_modifier(Modifier::DIM),
+    )));
+
+    let screen = f.size();
+    let width = screen.width.saturating_sub(4).min(72);
+    let height = (lines.len() as u16 + 2).min(screen.height.saturating_sub(2));
+    let area = Rect {
+        x: screen.x + (screen.width - width) / 2,
+        y: screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(tr!("tui.recap.title", branch = recap.branch));
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// "2일 3시간", "4h 10m"처럼 큰 단위 두 개까지만
+fn format_elapsed(elapsed: chrono::Duration) -> String {
+    let minutes = elapsed.num_minutes().max(0);
+    let (days, hours) = (minutes / (24 * 60), minutes / 60 % 24);
+    if days > 0 {
+        tr!("tui.elapsed.days", days = days, hours = hours)
+    } else if hours > 0 {
+        tr!("tui.elapsed.hours", hours = hours, minutes = minutes % 60)
+    } else if minutes > 0 {
+        tr!("tui.elapsed.minutes", minutes = minutes)
+    } else {
+        tr!("tui.elapsed.now")
     }
 }
 
@@ -947,6 +1971,51 @@ fn slice_from_col(text: &str, start_col: usize, max_cols: usize) -> String {
     out
 }
 
+/// `slice_from_col`과 같은 범위를 자르면서 검색어와 맞는 byte 범위를 강조한다.
+fn highlighted_spans(
+    text: &str,
+    ranges: &[Range<usize>],
+    start_col: usize,
+    max_cols: usize,
+) -> Vec<Span<'static>> {
+    let highlight = Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut current_hl = false;
+    let mut col = 0;
+
+    for (byte_idx, ch) in text.char_indices() {
+        let w = UnicodeWidthChar::width(ch).unwrap_or(0);
+        if col + w <= start_col {
+            col += w;
+            continue;
+        }
+        if max_cols == 0 || col >= start_col + max_cols {
+            break;
+        }
+        let hl = ranges.iter().any(|r| r.contains(&byte_idx));
+        if hl != current_hl && !current.is_empty() {
+            let text = std::mem::take(&mut current);
+            spans.push(if current_hl {
+                Span::styled(text, highlight)
+            } else {
+                Span::raw(text)
+            });
+        }
+        current_hl = hl;
+        current.push(ch);
+        col += w;
+    }
+    if !current.is_empty() {
+        spans.push(if current_hl {
+            Span::styled(current, highlight)
+        } else {
+            Span::raw(current)
+        });
+    }
+    spans
+}
+
 fn width_upto_char(text: &str, char_idx: usize) -> usize {
     let mut width = 0;
     for (i, ch) in text.chars().enumerate() {
@@ -958,6 +2027,20 @@ fn width_upto_char(text: &str, char_idx: usize) -> usize {
     width
 }
 
+/// 붙여넣은 텍스트를 커서 위치에 넣는다. 입력칸은 한 줄이라 줄바꿈은 공백으로 바꾼다.
+fn insert_pasted(input: &mut String, cursor: &mut usize, pasted: &str) {
+    let pasted: String = pasted
+        .trim_end_matches(['\r', '\n'])
+        .replace("\r\n", "\n")
+        .chars()
+        .map(|c| if c == '\n' || c == '\r' { ' ' } else { c })
+        .filter(|c| !c.is_control())
+        .collect();
+    let idx = byte_index_from_char(input, *cursor);
+    input.insert_str(idx, &pasted);
+    *cursor += pasted.chars().count();
+}
+
 fn byte_index_from_char(text: &str, char_idx: usize) -> usize {
     if char_idx == 0 {
         return 0;
@@ -1000,14 +2083,14 @@ fn debug_lines(
     viewport: Rect,
     final_cursor_abs: Option<(u16, u16)>,
 ) -> Vec<Line<'static>> {
-    let (rows, cols) = ui.pty.size();
-    let cursor = ui.pty.cursor_state();
+    let (rows, cols) = ui.terms.active().size();
+    let cursor = ui.terms.active().cursor_state();
     let cursor_line = cursor
         .as_ref()
         .map(|c| format!("cursor(raw): row={}, col={}", c.row, c.col))
         .unwrap_or_else(|| "cursor(raw): (hidden)".to_string());
     let draw_cursor = cursor.map(|c| c.draw).unwrap_or(false);
-    let follow = ui.pty.scroll_offset() == 0;
+    let follow = ui.terms.active().scroll_offset() == 0;
     let final_cursor_line = final_cursor_abs
         .map(|(x, y)| format!("cursor(abs): {},{}", x, y))
         .unwrap_or_else(|| "cursor(abs): (not drawn)".to_string());
@@ -1027,11 +2110,14 @@ fn debug_lines(
         Line::from(final_cursor_line),
         Line::from(format!("pty size: {}x{}", rows, cols)),
         Line::from(format!("viewport: {}x{}", viewport.height, viewport.width)),
-        Line::from(format!("scroll_offset: {}", ui.pty.scroll_offset())),
+        Line::from(format!(
+            "scroll_offset: {}",
+            ui.terms.active().scroll_offset()
+        )),
         Line::from(format!("follow: {}", if follow { "yes" } else { "no" })),
         Line::from(format!(
             "alt_screen: {}",
-            if ui.pty.alternate_screen() {
+            if ui.terms.active().alternate_screen() {
                 "yes"
             } else {
                 "no"
@@ -1045,5 +2131,5 @@ fn debug_lines(
 }
 
 fn to_io_error(e: String) -> io::Error {
-    io::Error::new(io::ErrorKind::Other, e)
+    io::Error::other(e)
 }
@@ -0,0 +1,81 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+
+use notify::event::{AccessKind, AccessMode};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::i18n::tr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchEvent {
+    /// 로그 디렉터리의 *.json이 바뀜 (다른 터미널의 `add` 등). 바뀐 파일 경로를 담는다.
+    Logs(PathBuf),
+    /// .git/HEAD가 바뀜 (브랜치 전환)
+    Head,
+}
+
+/// 로그 디렉터리와 git 디렉터리를 감시해서 TUI 이벤트 루프로 넘긴다.
+pub struct FileWatcher {
+    _watcher: RecommendedWatcher,
+    rx: Receiver<WatchEvent>,
+    log_dir: PathBuf,
+}
+
+impl FileWatcher {
+    pub fn start(log_dir: &Path, git_dir: Option<&Path>) -> Result<Self, String> {
+        let (tx, rx) = mpsc::channel();
+        let head = git_dir.map(|dir| dir.join("HEAD"));
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+            let Ok(event) = res else {
+                return;
+            };
+            if !is_change(&event.kind) {
+                return;
+            }
+            for path in &event.paths {
+                let kind = if head.as_deref() == Some(path.as_path()) {
+                    WatchEvent::Head
+                } else if path.extension().is_some_and(|ext| ext == "json") {
+                    WatchEvent::Logs(path.clone())
+                } else {
+                    continue;
+                };
+                let _ = tx.send(kind);
+            }
+        })
+        .map_err(|e| tr!("watch.start_failed", error = e))?;
+
+        watcher
+            .watch(log_dir, RecursiveMode::NonRecursive)
+            .map_err(|e| tr!("watch.path_failed", error = e, path = log_dir.display()))?;
+        // git은 HEAD.lock을 HEAD로 rename하므로 파일이 아니라 디렉터리를 감시한다
+        if let Some(git_dir) = git_dir {
+            watcher
+                .watch(git_dir, RecursiveMode::NonRecursive)
+                .map_err(|e| tr!("watch.path_failed", error = e, path = git_dir.display()))?;
+        }
+
+        Ok(Self {
+            _watcher: watcher,
+            rx,
+            log_dir: log_dir.to_path_buf(),
+        })
+    }
+
+    pub fn log_dir(&self) -> &Path {
+        &self.log_dir
+    }
+
+    /// 쌓인 이벤트를 비운다 (블로킹 없음)
+    pub fn drain(&self) -> impl Iterator<Item = WatchEvent> + '_ {
+        self.rx.try_iter()
+    }
+}
+
+fn is_change(kind: &EventKind) -> bool {
+    match kind {
+        EventKind::Access(AccessKind::Close(AccessMode::Write)) => true,
+        EventKind::Access(_) => false,
+        _ => true,
+    }
+}
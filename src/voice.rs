@@ -1,16 +1,17 @@
 use std::sync::{
-    Arc,
-    Mutex,
-    Once,
+    Arc, Mutex, Once,
     atomic::{AtomicBool, AtomicU8, Ordering},
 };
 use std::time::{Duration, Instant};
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{SampleFormat, StreamConfig};
-use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
-use whisper_rs::whisper_rs_sys::ggml_log_level;
+use serde::{Deserialize, Serialize};
 use std::ffi::{c_char, c_void};
+use whisper_rs::whisper_rs_sys::ggml_log_level;
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+use crate::i18n::tr;
 
 pub mod model;
 
@@ -22,7 +23,8 @@ pub struct VoiceRecording {
     channels: u16,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
 pub struct VadConfig {
     pub frame_ms: u32,
     pub start_threshold: f32,
@@ -49,7 +51,11 @@ impl Default for VadConfig {
 }
 
 #[allow(dead_code)]
-pub fn transcribe_from_mic(duration: Duration, model_path: &str) -> Result<String, String> {
+pub fn transcribe_from_mic(
+    duration: Duration,
+    model_path: &str,
+    language: &str,
+) -> Result<String, String> {
     silence_whisper_logs();
     let recorder = start_recording()?;
     let start = Instant::now();
@@ -57,15 +63,20 @@ pub fn transcribe_from_mic(duration: Duration, model_path: &str) -> Result<Strin
         std::thread::sleep(Duration::from_millis(10));
     }
     let (audio, input_rate, channels) = recorder.stop();
-    transcribe_audio(model_path, audio, input_rate, channels)
+    transcribe_audio(model_path, language, audio, input_rate, channels)
 }
 
-pub fn transcribe_from_mic_vad(model_path: &str, config: VadConfig) -> Result<String, String> {
-    transcribe_from_mic_vad_with_cancel(model_path, config, None)
+pub fn transcribe_from_mic_vad(
+    model_path: &str,
+    language: &str,
+    config: VadConfig,
+) -> Result<String, String> {
+    transcribe_from_mic_vad_with_cancel(model_path, language, config, None)
 }
 
 pub fn transcribe_from_mic_vad_with_cancel(
     model_path: &str,
+    language: &str,
     config: VadConfig,
     cancel: Option<Arc<AtomicBool>>,
 ) -> Result<String, String> {
@@ -77,7 +88,7 @@ pub fn transcribe_from_mic_vad_with_cancel(
         (recorder.sample_rate as u64 * config.frame_ms as u64 / 1000) as usize;
     let channels = recorder.channels as usize;
     if frame_samples_per_channel == 0 || channels == 0 {
-        return Err("입력 샘플레이트가 너무 낮습니다".to_string());
+        return Err(tr!("voice.sample_rate_low"));
     }
     let frame_samples = frame_samples_per_channel * channels;
     let pre_roll_samples =
@@ -95,7 +106,7 @@ pub fn transcribe_from_mic_vad_with_cancel(
             .unwrap_or(false)
         {
             drop(recorder);
-            return Err("녹음이 취소되었습니다".to_string());
+            return Err(tr!("voice.cancelled"));
         }
 
         if start.elapsed() > Duration::from_millis(config.max_record_ms as u64) {
@@ -104,7 +115,7 @@ pub fn transcribe_from_mic_vad_with_cancel(
                 break data.len();
             } else {
                 drop(recorder);
-                return Err("음성이 감지되지 않았습니다".to_string());
+                return Err(tr!("voice.no_speech"));
             }
         }
 
@@ -121,7 +132,10 @@ pub fn transcribe_from_mic_vad_with_cancel(
         let (rms, frame_end) = {
             let data = recorder.buffer.lock().unwrap();
             let frame = &data[processed..processed + frame_samples];
-            (rms_energy(frame, recorder.channels), processed + frame_samples)
+            (
+                rms_energy(frame, recorder.channels),
+                processed + frame_samples,
+            )
         };
 
         let voiced = rms >= config.start_threshold;
@@ -154,7 +168,7 @@ pub fn transcribe_from_mic_vad_with_cancel(
         .unwrap_or(false)
     {
         drop(recorder);
-        return Err("녹음이 취소되었습니다".to_string());
+        return Err(tr!("voice.cancelled"));
     }
 
     let mut data = recorder.buffer.lock().unwrap().clone();
@@ -163,15 +177,16 @@ pub fn transcribe_from_mic_vad_with_cancel(
     drop(recorder);
 
     if speech_end <= speech_start || speech_end > data.len() {
-        return Err("유효한 음성 구간을 찾지 못했습니다".to_string());
+        return Err(tr!("voice.no_segment"));
     }
 
     let audio = data.drain(speech_start..speech_end).collect::<Vec<_>>();
-    transcribe_audio(model_path, audio, sample_rate, channels)
+    transcribe_audio(model_path, language, audio, sample_rate, channels)
 }
 
 pub fn transcribe_from_mic_until_signal(
     model_path: &str,
+    language: &str,
     signal: Arc<AtomicU8>,
 ) -> Result<String, String> {
     silence_whisper_logs();
@@ -181,25 +196,25 @@ pub fn transcribe_from_mic_until_signal(
         if state != 0 {
             if state == RECORD_SIGNAL_CANCEL {
                 drop(recorder);
-                return Err("녹음이 취소되었습니다".to_string());
+                return Err(tr!("voice.cancelled"));
             }
             break;
         }
         std::thread::sleep(Duration::from_millis(50));
     }
     let (audio, input_rate, channels) = recorder.stop();
-    transcribe_audio(model_path, audio, input_rate, channels)
+    transcribe_audio(model_path, language, audio, input_rate, channels)
 }
 
 pub fn start_recording() -> Result<VoiceRecording, String> {
     let host = cpal::default_host();
     let device = host
         .default_input_device()
-        .ok_or_else(|| "마이크 장치가 없습니다".to_string())?;
+        .ok_or_else(|| tr!("voice.no_mic"))?;
 
     let supported = device
         .default_input_config()
-        .map_err(|e| format!("입력 디바이스 설정 실패: {}", e))?;
+        .map_err(|e| tr!("voice.device_config_failed", error = e))?;
 
     let sample_rate = supported.sample_rate().0;
     let channels = supported.channels();
@@ -221,7 +236,7 @@ pub fn start_recording() -> Result<VoiceRecording, String> {
                 err_fn,
                 None,
             )
-            .map_err(|e| format!("스트림 생성 실패: {}", e))?,
+            .map_err(|e| tr!("voice.stream_failed", error = e))?,
         SampleFormat::I16 => device
             .build_input_stream(
                 &config,
@@ -232,26 +247,27 @@ pub fn start_recording() -> Result<VoiceRecording, String> {
                 err_fn,
                 None,
             )
-            .map_err(|e| format!("스트림 생성 실패: {}", e))?,
+            .map_err(|e| tr!("voice.stream_failed", error = e))?,
         SampleFormat::U16 => device
             .build_input_stream(
                 &config,
                 move |data: &[u16], _| {
                     let mut b = buffer_clone.lock().unwrap();
-                    b.extend(data.iter().map(|&s| {
-                        (s as f32 / u16::MAX as f32) * 2.0 - 1.0
-                    }));
+                    b.extend(
+                        data.iter()
+                            .map(|&s| (s as f32 / u16::MAX as f32) * 2.0 - 1.0),
+                    );
                 },
                 err_fn,
                 None,
             )
-            .map_err(|e| format!("스트림 생성 실패: {}", e))?,
-        _ => return Err("지원하지 않는 샘플 포맷".to_string()),
+            .map_err(|e| tr!("voice.stream_failed", error = e))?,
+        _ => return Err(tr!("voice.unsupported_format")),
     };
 
     stream
         .play()
-        .map_err(|e| format!("스트림 재생 실패: {}", e))?;
+        .map_err(|e| tr!("voice.stream_play_failed", error = e))?;
 
     Ok(VoiceRecording {
         stream,
@@ -272,16 +288,17 @@ impl VoiceRecording {
 
 pub fn transcribe_audio(
     model_path: &str,
+    language: &str,
     audio: Vec<f32>,
     input_rate: u32,
     channels: u16,
 ) -> Result<String, String> {
     silence_whisper_logs();
     if audio.is_empty() {
-        return Err("녹음된 오디오가 비어 있음".to_string());
+        return Err(tr!("voice.empty_audio"));
     }
     let audio_16k = to_16k_mono(audio, input_rate, channels);
-    transcribe_whisper(model_path, &audio_16k)
+    transcribe_whisper(model_path, language, &audio_16k)
 }
 
 /// interleaved f32 → mono + 16kHz
@@ -354,31 +371,35 @@ fn rms_energy(frame: &[f32], channels: u16) -> f32 {
     ((sum_sq / frames as f64) as f32).sqrt()
 }
 
-fn transcribe_whisper(model_path: &str, audio_16k: &[f32]) -> Result<String, String> {
+fn transcribe_whisper(
+    model_path: &str,
+    language: &str,
+    audio_16k: &[f32],
+) -> Result<String, String> {
     let ctx = WhisperContext::new_with_params(model_path, WhisperContextParameters::default())
-        .map_err(|e| format!("Whisper 모델 로드 실패: {}", e))?;
+        .map_err(|e| tr!("voice.whisper_load_failed", error = e))?;
 
     let mut state = ctx
         .create_state()
-        .map_err(|e| format!("Whisper state 생성 실패: {}", e))?;
+        .map_err(|e| tr!("voice.whisper_state_failed", error = e))?;
 
     let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
-    params.set_language(Some("ko"));
+    params.set_language(Some(language));
     params.set_translate(false);
 
     state
         .full(params, audio_16k)
-        .map_err(|e| format!("Whisper 추론 실패: {}", e))?;
+        .map_err(|e| tr!("voice.whisper_failed", error = e))?;
 
     let n = state
         .full_n_segments()
-        .map_err(|e| format!("세그먼트 읽기 실패: {}", e))?;
+        .map_err(|e| tr!("voice.segment_failed", error = e))?;
     let mut result = String::new();
 
     for i in 0..n {
         let seg = state
             .full_get_segment_text(i)
-            .map_err(|e| format!("세그먼트 텍스트 읽기 실패: {}", e))?;
+            .map_err(|e| tr!("voice.segment_text_failed", error = e))?;
         result.push_str(&seg);
     }
 
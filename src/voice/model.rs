@@ -2,8 +2,9 @@ use std::fs::{self, File};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
-const MODEL_URL: &str =
-    "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin";
+use crate::i18n::tr;
+
+/// URL에서 파일 이름을 알 수 없을 때 쓰는 이름
 const MODEL_FILENAME: &str = "ggml-base.bin";
 
 pub struct PreparedModel {
@@ -11,11 +12,14 @@ pub struct PreparedModel {
     pub downloaded: bool,
 }
 
-pub fn prepare_model_path_with_status<F>(mut on_status: F) -> Result<PreparedModel, String>
+pub fn prepare_model_path_with_status<F>(
+    model_url: &str,
+    mut on_status: F,
+) -> Result<PreparedModel, String>
 where
     F: FnMut(&str),
 {
-    let path = resolve_model_path()?;
+    let path = resolve_model_path(model_url)?;
     if path.exists() {
         if path.is_file() {
             return Ok(PreparedModel {
@@ -23,54 +27,73 @@ where
                 downloaded: false,
             });
         }
-        return Err("모델 경로가 파일이 아닙니다".to_string());
+        return Err(tr!("model.not_file"));
     }
 
-    on_status("모델이 없어 다운로드합니다...");
-    download_model(&path)?;
-    on_status("모델 다운로드 완료");
+    on_status(&tr!("model.downloading"));
+    download_model(model_url, &path)?;
+    on_status(&tr!("model.downloaded"));
     Ok(PreparedModel {
         path: path_to_string(path)?,
         downloaded: true,
     })
 }
 
-fn resolve_model_path() -> Result<PathBuf, String> {
+fn resolve_model_path(model_url: &str) -> Result<PathBuf, String> {
     if let Ok(path) = std::env::var("WHISPER_MODEL") {
         return Ok(PathBuf::from(path));
     }
 
-    default_model_path()
+    default_model_path(model_url)
+}
+
+/// voice.model_url을 바꾸면 다른 파일로 받도록 URL의 마지막 경로 이름을 쓴다
+fn default_model_path(model_url: &str) -> Result<PathBuf, String> {
+    let cwd = std::env::current_dir().map_err(|e| tr!("model.cwd_failed", error = e))?;
+    Ok(cwd.join("models").join(model_filename(model_url)))
 }
 
-fn default_model_path() -> Result<PathBuf, String> {
-    let cwd = std::env::current_dir()
-        .map_err(|e| format!("현재 디렉토리를 가져올 수 없습니다: {}", e))?;
-    Ok(cwd.join("models").join(MODEL_FILENAME))
+fn model_filename(model_url: &str) -> String {
+    let path = model_url.split(['?', '#']).next().unwrap_or_default();
+    // 호스트 뒤의 경로만 본다
+    let name = path
+        .split_once("://")
+        .and_then(|(_, rest)| rest.split_once('/'))
+        .and_then(|(_, path)| path.rsplit('/').next())
+        .unwrap_or_default();
+    let safe = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+    if safe {
+        name.to_string()
+    } else {
+        MODEL_FILENAME.to_string()
+    }
 }
 
-fn download_model(path: &Path) -> Result<(), String> {
+fn download_model(model_url: &str, path: &Path) -> Result<(), String> {
     if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)
-            .map_err(|e| format!("모델 디렉토리 생성 실패: {}", e))?;
+        fs::create_dir_all(parent).map_err(|e| tr!("model.dir_create_failed", error = e))?;
     }
 
     let tmp_path = path.with_extension("part");
     let result = (|| {
-        let mut response =
-            reqwest::blocking::get(MODEL_URL).map_err(|e| format!("다운로드 요청 실패: {}", e))?;
+        let mut response = reqwest::blocking::get(model_url)
+            .map_err(|e| tr!("model.request_failed", error = e))?;
         response = response
             .error_for_status()
-            .map_err(|e| format!("다운로드 응답 오류: {}", e))?;
+            .map_err(|e| tr!("model.response_error", error = e))?;
 
-        let mut file = File::create(&tmp_path)
-            .map_err(|e| format!("임시 파일 생성 실패: {}", e))?;
+        let mut file =
+            File::create(&tmp_path).map_err(|e| tr!("model.tmp_create_failed", error = e))?;
         std::io::copy(&mut response, &mut file)
-            .map_err(|e| format!("다운로드 저장 실패: {}", e))?;
+            .map_err(|e| tr!("model.save_download_failed", error = e))?;
         file.flush()
-            .map_err(|e| format!("다운로드 파일 플러시 실패: {}", e))?;
+            .map_err(|e| tr!("model.flush_failed", error = e))?;
 
-        fs::rename(&tmp_path, path).map_err(|e| format!("모델 파일 저장 실패: {}", e))?;
+        fs::rename(&tmp_path, path).map_err(|e| tr!("model.save_failed", error = e))?;
         Ok(())
     })();
 
@@ -84,5 +107,30 @@ fn download_model(path: &Path) -> Result<(), String> {
 fn path_to_string(path: PathBuf) -> Result<String, String> {
     path.to_str()
         .map(|s| s.to_string())
-        .ok_or_else(|| "모델 경로가 UTF-8이 아닙니다".to_string())
+        .ok_or_else(|| tr!("model.path_utf8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn model_file_follows_url() {
+        assert_eq!(
+            model_filename(
+                "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.bin?download=true"
+            ),
+            "ggml-small.bin"
+        );
+        assert_eq!(
+            model_filename("https://example.com/models/ggml-base.en.bin"),
+            "ggml-base.en.bin"
+        );
+        assert_eq!(
+            model_filename("https://example.com/models/"),
+            MODEL_FILENAME
+        );
+        assert_eq!(model_filename("https://example.com/../.."), MODEL_FILENAME);
+        assert_eq!(model_filename("https://example.com"), MODEL_FILENAME);
+    }
 }